@@ -23,23 +23,7 @@ impl Solution<DAY_01> for Solutions {
     type Output = u64;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        puzzle
-            .split("\n\n")
-            .map(|set| {
-                set.lines()
-                    .map(str::parse::<u64>)
-                    .map(Result::unwrap)
-                    .sum::<u64>()
-            })
-            .fold([0, 0, 0], |mut acc, n| {
-                for value in &mut acc {
-                    if n > *value {
-                        *value = n;
-                        break;
-                    }
-                }
-                acc
-            })
+        parse_input(Source::new(DAY_01, puzzle)).or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
@@ -60,4 +44,24 @@ impl Test<DAY_01> for Solutions {
     }
 }
 
-derive_tests!(Solutions, DAY_01);
\ No newline at end of file
+derive_tests!(Solutions, DAY_01);
+
+fn parse_input(src: Source<'_>) -> ParseResult<[u64; 3]> {
+    src.puzzle()
+        .split("\n\n")
+        .map(|set| {
+            set.lines()
+                .map(|line| src.parse::<u64>(line))
+                .sum::<ParseResult<u64>>()
+        })
+        .try_fold([0, 0, 0], |mut acc, n| {
+            let n = n?;
+            for value in &mut acc {
+                if n > *value {
+                    *value = n;
+                    break;
+                }
+            }
+            Ok(acc)
+        })
+}
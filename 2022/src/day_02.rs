@@ -18,27 +18,32 @@
 //! two more maps (one to convert the desired outcome into an actual shape to play, one to compute_outcome) before again summing
 //! to get the answer.
 
+use std::str::FromStr;
+
 use super::*;
 use Shape::*;
 use DesiredOutcome::*;
 
 impl Solution<DAY_02> for Solutions {
-    type Input<'a> = Vec<&'a str>;
+    type Input<'a> = Vec<(Shape, Shape, DesiredOutcome)>;
     type Output = u64;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        puzzle.lines().collect()
+        let src = Source::new(DAY_02, puzzle);
+
+        src.lines()
+            .map(|line| {
+                let (c, r) = src.split_once(line, ' ')?;
+                Ok((src.parse(c)?, src.parse(r)?, src.parse(r)?))
+            })
+            .collect::<ParseResult<_>>()
+            .or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
         input
             .iter()
-            .map(|line| {
-                line
-                    .split_once(' ')
-                    .map(|(c, r)| (Shape::from(c), Shape::from(r)))
-                    .unwrap()
-            })
+            .map(|(challenge, response, _)| (*challenge, *response))
             .map(Shape::compute_outcome)
             .sum::<u64>()
     }
@@ -46,12 +51,7 @@ impl Solution<DAY_02> for Solutions {
     fn part_two(input: &Self::Input<'_>) -> Self::Output {
         input
             .iter()    
-            .map(|line| {
-                line
-                    .split_once(' ')
-                    .map(|(c, r)| (Shape::from(c), DesiredOutcome::from(r)))
-                    .unwrap()
-            })
+            .map(|(challenge, _, outcome)| (*challenge, *outcome))
             .map(|(challenge, outcome)| {
                 (challenge, match outcome {
                     Loss => challenge.wins_against(),
@@ -77,19 +77,21 @@ derive_tests!(Solutions, DAY_02);
 
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Shape {
+pub enum Shape {
     Rock,
     Paper,
     Scissors
 }
 
-impl From<&str> for Shape {
-    fn from(char: &str) -> Self {
+impl FromStr for Shape {
+    type Err = &'static str;
+
+    fn from_str(char: &str) -> Result<Self, Self::Err> {
         match char {
-            "A" | "X" => Self::Rock,
-            "B" | "Y" => Self::Paper,
-            "C" | "Z" => Self::Scissors,
-            _ => unreachable!()
+            "A" | "X" => Ok(Self::Rock),
+            "B" | "Y" => Ok(Self::Paper),
+            "C" | "Z" => Ok(Self::Scissors),
+            _ => Err("expected A, B, C, X, Y or Z")
         }
     }
 }
@@ -130,19 +132,22 @@ impl Shape {
     }
 }
 
-enum DesiredOutcome {
+#[derive(Debug, Clone, Copy)]
+pub enum DesiredOutcome {
     Loss,
     Draw,
     Win
 }
 
-impl From<&str> for DesiredOutcome {
-    fn from(char: &str) -> Self {
+impl FromStr for DesiredOutcome {
+    type Err = &'static str;
+
+    fn from_str(char: &str) -> Result<Self, Self::Err> {
         match char {
-            "X" => Self::Loss,
-            "Y" => Self::Draw,
-            "Z" => Self::Win,
-            _ => unreachable!()
+            "X" => Ok(Self::Loss),
+            "Y" => Ok(Self::Draw),
+            "Z" => Ok(Self::Win),
+            _ => Err("expected X, Y or Z")
         }
     }
 }
\ No newline at end of file
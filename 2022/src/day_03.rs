@@ -23,7 +23,7 @@ impl Solution<DAY_03> for Solutions {
     type Output = u64;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        puzzle.lines().collect()
+        parse_input(Source::new(DAY_03, puzzle)).or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
@@ -61,6 +61,24 @@ impl Test<DAY_03> for Solutions {
 
 derive_tests!(Solutions, DAY_03);
 
+fn parse_input(src: Source<'_>) -> ParseResult<Vec<&str>> {
+    let rucksacks: Vec<_> = src.lines().collect();
+
+    for line in &rucksacks {
+        src.chars(line, |c| c.is_ascii_alphabetic().then_some(c))?;
+
+        if line.len() % 2 != 0 {
+            return Err(src.error(line, "rucksack can't be split into two equal compartments"));
+        }
+    }
+
+    if rucksacks.len() % 3 != 0 {
+        return Err(src.eof("expected the rucksacks to divide evenly into groups of three"));
+    }
+
+    Ok(rucksacks)
+}
+
 fn find_array_intersection<const N: usize>(set: [&str; N]) -> Vec<char> {
     let mut intersection = set[0]
         .chars()
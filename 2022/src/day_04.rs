@@ -23,18 +23,19 @@ impl Solution<DAY_04> for Solutions {
     type Output = usize;
 
     fn parse(puzzle: &'_ str) -> Self::Input<'_> {
-        puzzle
-            .lines()
-            .map(|line| {
-                line
-                    .split_once(',')
-                    .unwrap()
+        let src = Source::new(DAY_04, puzzle);
+
+        src.lines()
+            .map(|line| src.split_once(line, ','))
+            .map(|pair| {
+                let (left, right) = pair?;
+                Ok((
+                    pair_to_range(src, left)?,
+                    pair_to_range(src, right)?
+                ))
             })
-            .map(|(left, right)| (
-                pair_to_range(left),
-                pair_to_range(right)
-            ))
-            .collect()
+            .collect::<ParseResult<_>>()
+            .or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
@@ -69,15 +70,7 @@ impl Test<DAY_04> for Solutions {
 
 derive_tests!(Solutions, DAY_04);
 
-fn pair_to_range(pair: &str) -> Range<u64> {
-    pair
-        .split_once('-')
-        .map(|(l, r)| {
-            (
-                l.parse::<u64>().unwrap(),
-                r.parse::<u64>().unwrap()
-            )
-        })
-        .map(|(l, r)| l..r)
-        .unwrap()
+fn pair_to_range<'i>(src: Source<'i>, pair: &'i str) -> ParseResult<Range<u64>> {
+    let (l, r) = src.split_once(pair, '-')?;
+    Ok(src.parse::<u64>(l)?..src.parse::<u64>(r)?)
 }
\ No newline at end of file
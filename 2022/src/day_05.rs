@@ -7,7 +7,7 @@
 //! "Oh God, it's actually just parsing hell." - Me, after reading the problem
 //! 
 //! ## Parsing
-//! This is what took most of my time. Parsing the commands is easy (see [`Command::from_str`]), but turning the
+//! This is what took most of my time. Parsing the commands is easy (see [`Command::from_str`](Command::parse)), but turning the
 //! little stack drawing into a well-typed data structure was... not.
 //! 
 //! My solution ended up using [`Iterator::skip`] and [`Iterator::step_by`] on the characters of each line to
@@ -31,36 +31,7 @@ impl Solution<DAY_05> for Solutions {
     type Output = String;
 
     fn parse(puzzle: &'_ str) -> Self::Input<'_> {
-        let (drawing, commands) = puzzle
-            .split_once("\n\n")
-            .unwrap();
-
-        let mut stacks = vec![Stack::new(); 9];
-        
-        drawing
-            .lines()
-            .map(|line| line
-                .chars()
-                .skip(1)
-                .step_by(4)
-                .collect::<Vec<_>>()
-            )
-            .for_each(|row| {
-                for i in 0..row.len() {
-                    stacks[i].push(row[i])
-                }
-            });
-        
-        for stack in &mut stacks {
-            stack.retain(|char| *char != ' ')
-        }
-
-        let commands: Vec<_> = commands
-            .lines()
-            .map(Command::from_str)
-            .collect();
-        
-        (stacks, commands)
+        parse_input(Source::new(DAY_05, puzzle)).or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
@@ -87,6 +58,39 @@ impl Test<DAY_05> for Solutions {
 
 derive_tests!(Solutions, DAY_05);
 
+fn parse_input(src: Source<'_>) -> ParseResult<(Vec<Stack>, Vec<Command>)> {
+    let (drawing, commands) = src.split_once(src.puzzle(), "\n\n")?;
+
+    let mut stacks = vec![Stack::new(); 9];
+    
+    for line in drawing.lines() {
+        let row: Vec<_> = line
+            .chars()
+            .skip(1)
+            .step_by(4)
+            .collect();
+
+        if row.len() > stacks.len() {
+            return Err(src.error(line, format!("expected at most {} stacks", stacks.len())));
+        }
+
+        for i in 0..row.len() {
+            stacks[i].push(row[i])
+        }
+    }
+    
+    for stack in &mut stacks {
+        stack.retain(|char| *char != ' ')
+    }
+
+    let commands = commands
+        .lines()
+        .map(|line| Command::parse(src, line))
+        .collect::<ParseResult<_>>()?;
+    
+    Ok((stacks, commands))
+}
+
 fn simulate_craning(commands: &[Command], stacks: &mut [Stack], reversed: bool) -> String {
     for cmd in commands {
         let source = &mut stacks[cmd.source];
@@ -118,19 +122,26 @@ pub struct Command {
 }
 
 impl Command {
-    pub fn from_str(s: &str) -> Self {
-        let values: Vec<_> = s
+    pub fn parse<'i>(src: Source<'i>, s: &'i str) -> ParseResult<Self> {
+        let values = s
             .split(' ')
             .skip(1)
             .step_by(2)
-            .map(str::parse::<usize>)
-            .map(Result::unwrap)
-            .collect();
-        
-        Self {
-            count: values[0],
-            source: values[1] - 1,
-            dest: values[2] - 1
+            .map(|value| src.parse::<usize>(value))
+            .collect::<ParseResult<Vec<_>>>()?;
+
+        let [count, source, dest] = values[..] else {
+            return Err(src.error(s, "expected \"move N from A to B\""));
+        };
+
+        if !(1..=9).contains(&source) || !(1..=9).contains(&dest) {
+            return Err(src.error(s, "stacks are numbered 1 through 9"));
         }
+        
+        Ok(Self {
+            count,
+            source: source - 1,
+            dest: dest - 1
+        })
     }
 }
\ No newline at end of file
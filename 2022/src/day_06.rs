@@ -40,9 +40,14 @@ impl Solution<DAY_06> for Solutions {
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        puzzle
-            .chars()
-            .collect()
+        let src = Source::new(DAY_06, puzzle);
+
+        src.chars(puzzle.trim_end(), |c| c.is_ascii_lowercase().then_some(c))
+            .and_then(|chars| match chars.is_empty() {
+                true => Err(src.eof("expected a datastream")),
+                false => Ok(chars)
+            })
+            .or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
@@ -74,15 +79,4 @@ fn uniqueness_check(slice: &[char]) -> bool {
     }
     
     true
-}
-
-impl Test<DAY_06> for Solutions {
-    fn expected(part: bool) -> Self::Output {
-        match part {
-            PART_ONE => 7,
-            PART_TWO => 19
-        }
-    }
-}
-
-derive_tests!(Solutions, DAY_06);
\ No newline at end of file
+}
\ No newline at end of file
@@ -59,59 +59,33 @@ use std::{collections::HashMap, path::PathBuf};
 
 use super::*;
 
+const SMALL_DIRECTORY: Param<usize> = Param::fixed(DAY_07, "small_directory", 100_000);
+const FILESYSTEM_SIZE: Param<usize> = Param::fixed(DAY_07, "filesystem_size", 70_000_000);
+const UPDATE_SIZE: Param<usize> = Param::fixed(DAY_07, "update_size", 30_000_000);
+
 impl Solution<DAY_07> for Solutions {
     type Input<'i> = Filesystem;
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        let mut fs = puzzle
-            .lines()
-            .filter(|line| !line.starts_with("$ ls"))
-            .fold(Filesystem::new(), |mut fs, line| {
-                if line.starts_with("$ cd ") {
-                    let path = line.trim_start_matches("$ cd ");
-                    match path {
-                        ".." => fs.ascend(),
-                        _ => fs.descend(path)
-                    }
-                }
-                else if line.starts_with("dir") {
-                    let (_, name) = line.split_once(' ').unwrap();
-                    fs.push_child(name);
-                }
-                else {
-                    let (left, right) = line.split_once(' ').unwrap();
-                    match left {
-                        "dir" => fs.push_child(right),
-                        _ => {
-                            let size = left.parse::<usize>().unwrap();
-                            fs.push_size(size);
-                        }
-                    }
-                }
-                fs
-            });
-
-        fs.update_sizes();
-        fs
+        parse_input(Source::new(DAY_07, puzzle)).or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
+        let threshold = SMALL_DIRECTORY.get();
+
         input.table
             .values()
-            .filter_map(|dir| match dir.deep_size {
-                0..=100_000 => Some(dir.deep_size),
-                _ => None
+            .filter_map(|dir| match dir.deep_size <= threshold {
+                true => Some(dir.deep_size),
+                false => None
             })
             .sum::<usize>()
     }
 
     fn part_two(input: &Self::Input<'_>) -> Self::Output {
-        const FILESYSTEM_SIZE: usize = 70_000_000;
-        const UPDATE_SIZE: usize = 30_000_000;
-
-        let available_space = FILESYSTEM_SIZE - (input.load("/").deep_size);
-        let needed_space = UPDATE_SIZE - available_space;
+        let available_space = FILESYSTEM_SIZE.get() - (input.load("/").deep_size);
+        let needed_space = UPDATE_SIZE.get() - available_space;
 
         input.table
             .values()
@@ -221,4 +195,44 @@ impl Filesystem {
     }
 }
 
-derive_tests!(Solutions, DAY_07);
\ No newline at end of file
+derive_tests!(Solutions, DAY_07);
+
+fn parse_input(src: Source<'_>) -> ParseResult<Filesystem> {
+    let mut fs = Filesystem::new();
+    let mut listed = Vec::new();
+
+    for line in src.lines().filter(|line| !line.starts_with("$ ls")) {
+        if let Some(path) = line.strip_prefix("$ cd ") {
+            match path {
+                ".." => fs.ascend(),
+                _ => fs.descend(path)
+            }
+            continue;
+        }
+
+        if !fs.table.contains_key(&fs.path) {
+            return Err(src.error(line, "listing outside of any directory"));
+        }
+
+        let (left, right) = src.split_once(line, ' ')?;
+        match left {
+            "dir" => {
+                fs.push_child(right);
+                listed.push((line, fs.path.join(right)));
+            },
+            _ => fs.push_size(src.parse::<usize>(left)?)
+        }
+    }
+
+    // Sizes are only known for directories that were entered and listed.
+    if let Some((line, _)) = listed.iter().find(|(_, path)| !fs.table.contains_key(path)) {
+        return Err(src.error(line, "directory listed but never entered"));
+    }
+
+    if !fs.table.contains_key(&PathBuf::from("/")) {
+        return Err(src.eof("the root directory is never entered"));
+    }
+
+    fs.update_sizes();
+    Ok(fs)
+}
\ No newline at end of file
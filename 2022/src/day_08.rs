@@ -11,7 +11,7 @@
 //! 
 //! ## Parsing
 //! As aformentioned, this problem basically screams "parse me into a 2D array" - so that's exactly what I did,
-//! additionally using [`char::to_digit`] to convert the tree heights to a [`Vec<Vec<u32>>`] (aka a [`Vec2D<u32>`]).
+//! additionally using [`char::to_digit`] to convert the tree heights to a [`Vec<Vec<u32>>`] (aka a `Vec2D<u32>`).
 //! 
 //! ## Solutions
 //! My original approach was just to write some functions that used `for` loops to step horizontally and vertically
@@ -25,26 +25,28 @@
 //! element in our 2D array *as an iterator*, so we can go wild with FP and banish all (well, most) of the imperative `for` loops back
 //! to the medieval land of C where they belong.
 //! 
-//! There are a few ways to handle this, but I chose the more ergonomic path by defining a *trait* - [`Grid<T>`] - to extend [`Vec2D<T>`] with.
+//! There are a few ways to handle this, but I chose the more ergonomic path by defining a *trait* - [`Grid<T>`] - to extend `Vec2D<T>` with.
 //! The interface of [`Grid<T>`] exposes:
-//! - [`north_of(row, col)`](Grid::north_of) - (as well as [`south_of`](Grid::south_of) and so on.) These methods receive the row and column indices
+//! - `north_of(row, col)` - (as well as `south_of` and so on.) These methods receive the row and column indices
 //! of an element in the underlying grid structure and yield an iterator over all the elements to its north/south/east/west.
-//! - [`enumerate_coordinates`](Grid::enumerate_coordinates) - yields an ordered iterator over all possible coordinate pairs within the grid, in the form of (row, column).
-//! - [`is_on_edge`](Grid::is_on_edge) - receives the row and column indices of an element and evaluates whether or not it is on the "edge" of the grid.
+//! - `enumerate_coordinates` - yields an ordered iterator over all possible coordinate pairs within the grid, in the form of (row, column).
+//! - `is_on_edge` - receives the row and column indices of an element and evaluates whether or not it is on the "edge" of the grid.
 //! 
-//! With the [`Grid<T>`] trait defined, I then provided a blanket implementation of the trait for all [`Vec<Vec<T>>`]/[`Vec2D<T>`] (with T being totally unbounded beyond
+//! With the [`Grid<T>`] trait defined, I then provided a blanket implementation of the trait for all [`Vec<Vec<T>>`]/`Vec2D<T>` (with T being totally unbounded beyond
 //! the implicit [`Sized`] bound.) Now all the functionality of [`Grid<T>`] is directly available as methods on any 2D array - once [`Grid<T>`] is in scope, of course.
 //! 
 //! This is a very convenient; the only downside is that (due to the limitations of trait methods) the returned iterators must incur the costs of being 
 //! [`Box`]ed (heap allocated) and dynamically dispatched rather than stack-allocated and statically dispatched.
 //! 
+//! (The trait has since been replaced by the shared [`Grid`] type, so the code below uses [`Grid::coordinates`] and [`Grid::ray`] in place of these methods.)
+//! 
 //! Now that we have a good interface to our tree height map, computing the actual solutions is relatively simple. 
 //! 
-//! For part one, we can use [`Grid::enumerate_coordinates`] and filter using the following algorithm:
+//! For part one, we can use `Grid::enumerate_coordinates` and filter using the following algorithm:
 //! 1. If the coordinate is on the edge, keep it and continue.
 //! 2. Otherwise, we need to scan along the axes of the coordinate to determine its visibility. 
 //! 3. We create a `[bool; 4]` to track which directions (if any) the current tree is visible from;
-//! then, we go through [`Grid::north_of`], [`Grid::south_of`] and so on, using the [`Iterator::any`] adaptor
+//! then, we go through `Grid::north_of`, `Grid::south_of` and so on, using the [`Iterator::any`] adaptor
 //! to scan the yielded iterators and see if any of the trees they contain are taller than the one we're currently inspecting, storing
 //! the result in the aformentioned bool array.
 //! 4. If any of the elements in the bool array are true, the tree is visible and we should keep its coordinates; otherwise, throw them out.
@@ -71,28 +73,18 @@
 use super::*;
 
 impl Solution<DAY_08> for Solutions {
-    type Input<'i> = Vec2D<u32>;
+    type Input<'i> = Grid<u32>;
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        let mut map = Vec::new();
-        puzzle
-            .lines()
-            .for_each(|line| {
-                let chars: Vec<_> = line
-                    .chars()
-                    .map(|char| char.to_digit(10))
-                    .map(Option::unwrap)
-                    .collect();
-                
-                map.push(chars);
-            });
-        map
+        Source::new(DAY_08, puzzle)
+            .grid(|char| char.to_digit(10))
+            .or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
         input
-            .enumerate_coordinates()
+            .coordinates()
             .filter(|coordinates| {
                 let (row, col) = *coordinates;
 
@@ -101,12 +93,12 @@ impl Solution<DAY_08> for Solutions {
                 }
             
                 let mut visible = [true; 4];
-                let root_height = input[row][col];
+                let root_height = input[(row, col)];
     
-                visible[0] = !input.north_of(row, col).any(|x| *x >= root_height);
-                visible[1] = !input.south_of(row, col).any(|x| *x >= root_height);
-                visible[2] = !input.east_of(row, col).any(|x| *x >= root_height);
-                visible[3] = !input.west_of(row, col).any(|x| *x >= root_height);
+                visible[0] = !input.ray((row, col), Direction::Up).any(|x| *x >= root_height);
+                visible[1] = !input.ray((row, col), Direction::Down).any(|x| *x >= root_height);
+                visible[2] = !input.ray((row, col), Direction::Right).any(|x| *x >= root_height);
+                visible[3] = !input.ray((row, col), Direction::Left).any(|x| *x >= root_height);
     
                 visible.iter().any(|vis| *vis)
             })           
@@ -115,10 +107,10 @@ impl Solution<DAY_08> for Solutions {
 
     fn part_two(input: &Self::Input<'_>) -> Self::Output {
         input
-            .enumerate_coordinates()
+            .coordinates()
             .map(|(row, col)| {
                 let mut scores = [0; 4];
-                let root_height = input[row][col];
+                let root_height = input[(row, col)];
 
                 let filter = |blocked: &mut bool, x: &u32| {
                     if *blocked { return None }
@@ -126,10 +118,10 @@ impl Solution<DAY_08> for Solutions {
                     Some(*x)
                 };
 
-                scores[0] = input.north_of(row, col).scan(false, filter).count();
-                scores[1] = input.south_of(row, col).scan(false, filter).count();
-                scores[2] = input.east_of(row, col).scan(false, filter).count();
-                scores[3] = input.west_of(row, col).scan(false, filter).count();
+                scores[0] = input.ray((row, col), Direction::Up).scan(false, filter).count();
+                scores[1] = input.ray((row, col), Direction::Down).scan(false, filter).count();
+                scores[2] = input.ray((row, col), Direction::Right).scan(false, filter).count();
+                scores[3] = input.ray((row, col), Direction::Left).scan(false, filter).count();
 
                 scores.iter().product::<usize>()
             })
@@ -147,71 +139,4 @@ impl Test<DAY_08> for Solutions {
     }
 }
 
-type Vec2D<T> = Vec<Vec<T>>;
-
-trait Grid<T> {
-    fn north_of(&self, row: usize, col: usize) -> Box<dyn Iterator<Item = &T> + '_>;
-    fn south_of(&self, row: usize, col: usize) -> Box<dyn Iterator<Item = &T> + '_>;
-    fn east_of(&self, row: usize, col: usize) -> Box<dyn Iterator<Item = &T> + '_>;
-    fn west_of(&self, row: usize, col: usize) -> Box<dyn Iterator<Item = &T> + '_>;
-    fn enumerate_coordinates(&self) -> Box<dyn Iterator<Item=(usize, usize)>>;
-    fn is_on_edge(&self, row: usize, col: usize) -> bool;
-}
-
-impl<T> Grid<T> for Vec2D<T> {
-    fn north_of(&self, row: usize, col: usize) -> Box<dyn Iterator<Item = &T> + '_> {
-        let range = 0..row;
-        let iterator = range
-            .rev()
-            .map(move |i| &self[i][col]);
-        
-        Box::new(iterator)
-    }
-
-    fn south_of(&self, row: usize, col: usize) -> Box<dyn Iterator<Item = &T> + '_> {
-        let range = (row + 1)..self[col].len();
-        let iterator = range
-            .into_iter()
-            .map(move |i| &self[i][col]);
-        
-        Box::new(iterator)
-    }
-
-    fn east_of(&self, row: usize, col: usize) -> Box<dyn Iterator<Item = &T> + '_> {
-        let range = (col + 1)..self[row].len();
-        let iterator = range
-            .into_iter()
-            .map(move |i| &self[row][i]);
-
-        Box::new(iterator)
-    }
-
-    fn west_of(&self, row: usize, col: usize) -> Box<dyn Iterator<Item = &T> + '_> {
-        let range = 0..col;
-        let iterator = range
-            .rev()
-            .map(move |i| &self[row][i]);
-        
-        Box::new(iterator)
-    }
-
-    fn enumerate_coordinates(&self) -> Box<dyn Iterator<Item=(usize, usize)>> {
-        let mut coordinates = Vec::new();
-
-        for (index, vec) in self.iter().enumerate() {
-            for i in 0..vec.len() {
-                coordinates.push((index, i))
-            }
-        }
-
-        Box::new(coordinates.into_iter())
-    }
-
-    fn is_on_edge(&self, row: usize, col: usize) -> bool {
-        row == 0 || row == self.len() - 1
-        ||
-        col == 0 || col == self[row].len() - 1
-    }
-}
-
 derive_tests!(Solutions, DAY_08);
\ No newline at end of file
@@ -58,14 +58,7 @@ impl Solution<DAY_09> for Solutions {
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        puzzle
-            .lines()
-            .flat_map(|line| {
-                let (dir, ct) = line.split_once(' ').unwrap();
-                let ct = ct.parse::<i32>().unwrap();
-                (0..ct).map(|_| dir.into())
-            })
-            .collect()
+        parse_input(Source::new(DAY_09, puzzle)).or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
@@ -91,15 +84,6 @@ impl Solution<DAY_09> for Solutions {
     }
 }
 
-impl Test<DAY_09> for Solutions {
-    fn expected(part: bool) -> Self::Output {
-        match part {
-            PART_ONE => 13,
-            PART_TWO => 1
-        }
-    }
-}
-
 #[derive(Debug)]
 struct Rope {
     knots: Vec<[i32; 2]>,
@@ -168,24 +152,18 @@ impl Rope {
     }
 }
 
-#[derive(Debug)]
-pub enum Direction {
-    Left,
-    Right,
-    Up,
-    Down
-}
-
-impl From<&str> for Direction {
-    fn from(str: &str) -> Self {
-        match str {
-            "L" => Self::Left,
-            "R" => Self::Right,
-            "U" => Self::Up,
-            "D" => Self::Down,
-            _ => unreachable!()
-        }
+fn parse_input(src: Source<'_>) -> ParseResult<Vec<Direction>> {
+    let mut moves = Vec::new();
+
+    for line in src.lines() {
+        let (dir, ct) = src.split_once(line, ' ')?;
+        let dir = match src.chars(dir, Direction::from_char)?[..] {
+            [dir] => dir,
+            _ => return Err(src.error(dir, "expected a single direction"))
+        };
+        let ct = src.parse::<u32>(ct)?;
+        moves.extend((0..ct).map(|_| dir));
     }
-}
 
-derive_tests!(Solutions, DAY_09);
\ No newline at end of file
+    Ok(moves)
+}
\ No newline at end of file
@@ -41,10 +41,12 @@ impl Solution<DAY_10> for Solutions {
     type Output = Split<isize, String>;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        puzzle
-            .lines()
-            .map(Instruction::from)
-            .collect()
+        let src = Source::new(DAY_10, puzzle);
+
+        src.lines()
+            .map(|line| Instruction::parse(src, line))
+            .collect::<ParseResult<_>>()
+            .or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
@@ -96,14 +98,14 @@ pub enum Instruction {
     Add(isize)
 }
 
-impl From<&str> for Instruction {
-    fn from(str: &str) -> Self {
+impl Instruction {
+    fn parse<'i>(src: Source<'i>, str: &'i str) -> ParseResult<Self> {
         if str == "noop" {
-            Self::Noop
+            Ok(Self::Noop)
         } else {
-            let (_, count) = str.split_once(' ').unwrap();
-            let count = count.parse::<isize>().unwrap();
-            Self::Add(count)
+            let count = src.strip_prefix(str, "addx ")?;
+            let count = src.parse::<isize>(count)?;
+            Ok(Self::Add(count))
         }
     }
 }
@@ -149,14 +151,19 @@ impl<'a> Processor<'a> {
 
 struct Screen {
     pixels: [[bool; 40]; 6],
-    beam: (usize, usize)
+    beam: (usize, usize),
+    /// Records the screen after every cycle.
+    #[cfg(feature = "viz")]
+    recorder: aoc_common::viz::Recorder,
 }
 
 impl Screen {
     pub fn new() -> Self {
         Self {
             pixels: [[false; 40]; 6],
-            beam: (0, 0)
+            beam: (0, 0),
+            #[cfg(feature = "viz")]
+            recorder: aoc_common::viz::Recorder::for_day(2022, 10),
         }
     }
 
@@ -166,12 +173,33 @@ impl Screen {
         if (rax as i32 - x as i32).abs() <= 1 {
             self.pixels[y][x] = true; 
         }
+
+        #[cfg(feature = "viz")]
+        self.record(rax);
         
         self.beam = match x + 1 {
             0..=39 => (x + 1, y),
             _ => (0, y + 1)
         };
     }
+
+    /// Push the lit pixels, the beam and the sprite (on the beam's row) to the recorder.
+    #[cfg(feature = "viz")]
+    fn record(&mut self, rax: isize) {
+        use aoc_common::viz::{BLACK, GREY, RED, YELLOW};
+
+        let (x, y) = self.beam;
+        let pixels = self.pixels;
+
+        self.recorder.frame(format_args!("beam at {x},{y}, x = {rax}"), 6, 40, |row, col| {
+            match pixels[row][col] {
+                true => YELLOW,
+                false if (row, col) == (y, x) => RED,
+                false if row == y && (col as isize - rax).abs() <= 1 => GREY,
+                false => BLACK
+            }
+        });
+    }
 }
 
 impl std::fmt::Display for Screen {
@@ -29,22 +29,25 @@ use std::collections::VecDeque;
 use super::*;
 use Operation::*;
 
+const ROUNDS_ONE: Param<usize> = Param::fixed(DAY_11, "rounds_one", 20);
+const ROUNDS_TWO: Param<usize> = Param::fixed(DAY_11, "rounds_two", 10_000);
+const RELIEF: Param<u64> = Param::fixed(DAY_11, "relief", 3);
+
 impl Solution<DAY_11> for Solutions {
     type Input<'i> = Vec<Monkey>;
     type Output = u64;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        puzzle
-            .split("\n\n")
-            .map(Monkey::from)
-            .collect()
+        parse_input(Source::new(DAY_11, puzzle)).or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
         let mut monkeys = input.clone();
 
-        for _ in 0..20 {
-            pass(&mut monkeys, |item| *item /= 3);
+        let relief = RELIEF.get();
+
+        for _ in 0..ROUNDS_ONE.get() {
+            pass(&mut monkeys, |item| *item /= relief);
         }
 
         compute_monkey_business(&monkeys)
@@ -60,7 +63,7 @@ impl Solution<DAY_11> for Solutions {
             .map(|monkey| monkey.divisor)
             .product::<u64>();
 
-        for _ in 0..10_000 {
+        for _ in 0..ROUNDS_TWO.get() {
             pass(&mut monkeys, |item| *item %= lcm);
         }
 
@@ -96,29 +99,17 @@ impl Operation {
     }
 }
 
-impl From<&str> for Operation {
-    fn from(expr: &str) -> Self {
-        let expr = expr.trim_start_matches("Operation: new = ");
-        let operand = match expr.contains('+') {
-            false => "*",
-            true => "+"
-        };
-    
-        let rhs = expr
-            .split_once(operand)
-            .map(|(_, rhs)| rhs.trim().parse::<u64>())
-            .unwrap();
-
-        match operand {
-            "*" => match rhs {
-                Ok(constant) => Mul(constant),
-                Err(_) => MulSelf
-            }
-            "+" => match rhs {
-                Ok(constant) => Add(constant),
-                Err(_) => AddSelf
-            }
-            _ => unreachable!()
+impl Operation {
+    fn parse<'i>(src: Source<'i>, expr: &'i str) -> ParseResult<Self> {
+        let expr = src.strip_prefix(expr, "Operation: new = old ")?;
+        let (operand, rhs) = src.split_once(expr, ' ')?;
+
+        match (operand, rhs) {
+            ("*", "old") => Ok(MulSelf),
+            ("+", "old") => Ok(AddSelf),
+            ("*", _) => Ok(Mul(src.parse::<u64>(rhs)?)),
+            ("+", _) => Ok(Add(src.parse::<u64>(rhs)?)),
+            _ => Err(src.error(operand, "expected '+' or '*'"))
         }
     }
 }
@@ -132,48 +123,66 @@ pub struct Monkey {
     inspections: u64
 }
 
-impl From<&str> for Monkey {
-    fn from(str: &str) -> Self {
+impl Monkey {
+    fn parse<'i>(src: Source<'i>, str: &'i str) -> ParseResult<Self> {
         let lines = str
             .lines()
             .map(str::trim)
             .skip(1)
             .collect::<Vec<_>>();
 
-        let items = lines[0]
-            .chars()
-            .filter(|char| char.is_numeric() || char == &',')
-            .collect::<String>()
+        let [items, operation, divisor, true_case, false_case] = lines[..] else {
+            return Err(src.error(str, "expected a monkey to be described in six lines"));
+        };
+
+        let items = src
+            .strip_prefix(items, "Starting items:")?
             .split(',')
-            .map(str::parse::<u64>)
-            .map(Result::unwrap)
-            .collect::<VecDeque<_>>();
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| src.parse::<u64>(item))
+            .collect::<ParseResult<VecDeque<_>>>()?;
 
-        let operation = Operation::from(lines[1]);
+        let operation = Operation::parse(src, operation)?;
         
-        let divisor = lines[2]
-            .trim_start_matches("Test: divisible by ")
-            .parse::<u64>()
-            .unwrap();
-
-        let true_case = lines[3]
-            .trim_start_matches("If true: throw to monkey ")
-            .parse::<u8>()
-            .unwrap();
+        let divisor = src.parse::<u64>(
+            src.strip_prefix(divisor, "Test: divisible by ")?
+        )?;
+
+        let true_case = src.parse::<u8>(
+            src.strip_prefix(true_case, "If true: throw to monkey ")?
+        )?;
         
-        let false_case = lines[4]
-            .trim_start_matches("If false: throw to monkey ")
-            .parse::<u8>()
-            .unwrap();
+        let false_case = src.parse::<u8>(
+            src.strip_prefix(false_case, "If false: throw to monkey ")?
+        )?;
 
-        Self {
+        Ok(Self {
             items,
             operation,
             divisor,
             targets: (true_case, false_case),
             inspections: 0
+        })
+    }
+}
+
+fn parse_input(src: Source<'_>) -> ParseResult<Vec<Monkey>> {
+    let blocks: Vec<_> = src.puzzle().split("\n\n").collect();
+    let monkeys = blocks
+        .iter()
+        .map(|block| Monkey::parse(src, block))
+        .collect::<ParseResult<Vec<_>>>()?;
+
+    for (monkey, block) in monkeys.iter().zip(&blocks) {
+        let (true_case, false_case) = monkey.targets;
+
+        if usize::from(true_case.max(false_case)) >= monkeys.len() {
+            return Err(src.error(block, format!("throws to a monkey that doesn't exist (there are {})", monkeys.len())));
         }
     }
+
+    Ok(monkeys)
 }
 
 fn pass(monkeys: &mut [Monkey], callback: impl Fn(&mut u64)) {
@@ -28,6 +28,8 @@
 //! ... but it works! It even accepts a closure callback that's used to decide if an edge is traversable from the current vertex,
 //! so both parts can optimize it to their needs.
 //! 
+//! (The search itself is now done by the shared [`search::bfs`]; [`Graph::shortest_path`] wraps it with the special cases and the filter.)
+//! 
 //! The solution functions themselves are nearly identical - only differing in their inputs to [`Graph::shortest_path`]. 
 //! - The first solution finds the starting index of `S` and queries shortest path with a target of `E` and a filter function of
 //! `edge <= vertex + 1`.
@@ -35,8 +37,9 @@
 //! `edge >= vertex - 1`.
 //! 
 //! This allows both parts to find the absolute shortest path in under 500 micros.
-
-use std::collections::{VecDeque, HashSet};
+//! 
+//! [`VecDeque`]: std::collections::VecDeque
+//! [`HashSet`]: std::collections::HashSet
 
 use super::*;
 
@@ -45,12 +48,7 @@ impl Solution<DAY_12> for Solutions {
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        let grid = puzzle
-            .lines()
-            .map(|line| line.chars().collect())
-            .collect();
-
-        Graph::unroll_grid(grid)
+        parse_input(Source::new(DAY_12, puzzle)).or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {        
@@ -141,47 +139,22 @@ impl Graph {
     pub fn shortest_path<F>(&self, start: usize, target: char, filter: F) -> Option<usize> where
         F: Fn(u8, u8) -> bool
     {
-        let mut queue = VecDeque::new();
-        let mut explored = HashSet::new();
-        let mut parents = vec![None; self.vertices.len()];
-
-        explored.insert(start);
-        queue.push_back(start);
-
-        while let Some(index) = queue.pop_front() {
-            let vertex = &self.vertices[index];
-
-            if vertex.value == target {
-                return parent_distance(&parents, index, 0).into()
-            }
-
-            let value = match vertex.value {
-                'S' => 'a',
-                'E' => 'z',
-                _ => vertex.value
-            };
+        let height = |index: usize| match self.vertices[index].value {
+            'S' => b'a',
+            'E' => b'z',
+            value => value as u8
+        };
 
-            vertex.edges
+        let neighbors = |index: &usize| {
+            self.vertices[*index].edges
                 .iter()
                 .copied()
-                .filter(|i| {
-                    let edge = self.vertices[*i].value;
-                    let edge = match edge {
-                        'S' => b'a',
-                        'E' => b'z',
-                        _ => edge as u8
-                    };
-                    filter(edge, value as u8)
-                })
-                .for_each(|i| {
-                    if explored.insert(i) {
-                        queue.push_back(i);
-                        parents[i] = Some(index);
-                    }
-                });
-        }
+                .filter(|edge| filter(height(*edge), height(*index)))
+                .collect::<Vec<_>>()
+        };
 
-        None
+        search::bfs([start], neighbors, |index| self.vertices[*index].value == target)
+            .map(|path| path.cost)
     }
 }
 
@@ -205,11 +178,21 @@ fn adjacent_indices(row: usize, col: usize, source: &'_[Vec<char>]) -> impl Iter
         })
 }
 
-fn parent_distance(parents: &[Option<usize>], index: usize, depth: usize) -> usize {
-    match parents[index] {
-        Some(parent) => parent_distance(parents, parent,depth + 1),
-        None => depth
+derive_tests!(Solutions, DAY_12);
+
+fn parse_input(src: Source<'_>) -> ParseResult<Graph> {
+    let grid = src.grid(|c| matches!(c, 'a'..='z' | 'S' | 'E').then_some(c))?;
+
+    for marker in ['S', 'E'] {
+        if grid.position(|c| *c == marker).is_none() {
+            return Err(src.eof(format!("the heightmap has no '{marker}'")));
+        }
     }
-}
 
-derive_tests!(Solutions, DAY_12);
\ No newline at end of file
+    let (rows, _) = grid.dimensions();
+    let grid = (0..rows)
+        .map(|row| grid.row(row).copied().collect())
+        .collect();
+
+    Ok(Graph::unroll_grid(grid))
+}
\ No newline at end of file
@@ -49,12 +49,7 @@ impl Solution<DAY_13> for Solutions {
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        puzzle
-            .lines()
-            .filter(|line| !line.is_empty())
-            .map(str::trim)
-            .map(Value::from)
-            .collect()
+        parse_input(Source::new(DAY_13, puzzle)).or_raise()
     }
 
     fn part_one(input: &Vec<Value>) -> Self::Output {  
@@ -69,8 +64,8 @@ impl Solution<DAY_13> for Solutions {
     fn part_two(input: &Vec<Value>) -> Self::Output {
         let mut input = input.clone();
         
-        let divider_a = Value::from("[[2]]");
-        let divider_b = Value::from("[[6]]");
+        let divider_a = Value::divider(2);
+        let divider_b = Value::divider(6);
 
         input.push(divider_a.clone());
         input.push(divider_b.clone());
@@ -100,56 +95,60 @@ pub enum Value {
     List(Vec<Value>)
 }
 
-impl From<&str> for Value {
-    fn from(data: &str) -> Self {
-        if data == "[]" {
-            return Self::List(vec![]);
-        }
+impl Value {
+    fn parse<'i>(src: Source<'i>, data: &'i str) -> ParseResult<Self> {
+        let Some(inner) = data.strip_prefix('[') else {
+            return src.parse::<u64>(data).map(Self::Data);
+        };
 
-        if let Ok(data) = data.trim().parse::<u64>() {
-            return Self::Data(data)
-        }
+        let inner = inner
+            .strip_suffix(']')
+            .ok_or_else(|| src.error(data, "unclosed list"))?;
 
-        let range = 1..data.len() - 1;
-        let chars = data[range].chars();
+        if inner.is_empty() {
+            return Ok(Self::List(vec![]));
+        }
 
         let mut list = Vec::new();
-        let mut buffer = String::new();
+        let mut start = 0;
         let mut depth = 0;
 
-        for character in chars {
+        // Split on top-level commas only, recursing into each element.
+        for (i, character) in inner.char_indices() {
             match character {
-                '[' => {
-                    depth += 1;
-                    buffer.push(character);
-                    continue;
-                }
-                ']' => {
-                    depth -= 1;
-                    buffer.push(character);
-                    continue;
-                }
+                '[' => depth += 1,
+                ']' if depth == 0 => return Err(src.error(&inner[i..], "unbalanced ']'")),
+                ']' => depth -= 1,
+                ',' if depth == 0 => {
+                    list.push(Self::parse(src, &inner[start..i])?);
+                    start = i + 1;
+                },
                 _ => ()
             }
-
-            if depth == 0 {
-                match character {
-                    ',' => {
-                        list.push(Self::from(buffer.as_str()));
-                        buffer.clear();
-                    }
-                    _ => buffer.push(character)
-                }
-            } else {
-                buffer.push(character);
-            }
         }
 
-        if !buffer.is_empty() {
-            list.push(Self::from(buffer.as_str()))
-        }
+        list.push(Self::parse(src, &inner[start..])?);
+
+        Ok(Self::List(list))
+    }
+
+    /// The divider packet `[[n]]`.
+    fn divider(n: u64) -> Self {
+        Self::List(vec![Self::List(vec![Self::Data(n)])])
+    }
+}
 
-        Self::List(list)
+fn parse_input(src: Source<'_>) -> ParseResult<Vec<Value>> {
+    let packets = src
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::trim)
+        .map(|line| Value::parse(src, line))
+        .collect::<ParseResult<Vec<_>>>()?;
+
+    match packets.len() % 2 {
+        0 => Ok(packets),
+        _ => Err(src.eof("expected the packets to come in pairs"))
     }
 }
 
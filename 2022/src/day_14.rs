@@ -31,7 +31,7 @@
 //! looks kinda like one. Also, it yielded a ~2000% speed boost over the naive approach, so...
 
 use std::{
-    collections::{HashSet, VecDeque},
+    collections::HashSet,
     ops::Range
 };
 
@@ -42,45 +42,7 @@ impl Solution<DAY_14> for Solutions {
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        let parse_line = |line: &&str| {
-            line
-                .split("->")
-                .map(|pair| {
-                    pair
-                        .trim()
-                        .split_once(',')
-                        .unwrap()
-                })
-                .map(|(l, r)| {
-                    (
-                        l.parse::<u64>().unwrap(),
-                        r.parse::<u64>().unwrap()
-                    )
-                })
-                .collect::<Vec<_>>()
-        };
-
-        let interpolate_line = |line: Vec<(u64, u64)>| {
-            line
-                .windows(2)
-                .flat_map(|w| cartesian_interpolate(&w[0], &w[1]))
-                .collect::<Vec<_>>()
-        };
-        
-        puzzle
-            .lines()
-            .fold(HashSet::new(), |mut acc, line| {
-                acc.insert(line);
-                acc
-            })
-            .iter()
-            .map(parse_line)
-            .map(interpolate_line)
-            .fold(HashSet::new(), |mut acc, coord| {
-                acc.extend(coord);
-                acc
-            })
-            .into()
+        parse_input(Source::new(DAY_14, puzzle)).or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
@@ -129,28 +91,19 @@ impl Solution<DAY_14> for Solutions {
     }
 
     fn part_two(input: &Self::Input<'_>) -> Self::Output {        
-        let mut queue = VecDeque::new();
-        let mut explored = HashSet::new();
-
-        explored.insert(START_POSITION);
-        queue.push_back(START_POSITION);
-
-        while let Some(position) = queue.pop_front() {            
-            if position.1 > input.y_max {
-                continue;
-            }
-            
-            possible_positions(position)
+        let neighbors = |position: &(u64, u64)| {
+            // Sand can't fall any further than the floor, so we stop exploring there.
+            let positions = match position.1 > input.y_max {
+                false => possible_positions(*position).to_vec(),
+                true => vec![]
+            };
+
+            positions
                 .into_iter()
                 .filter(|pos| !input.filled.contains(pos))
-                .for_each(|pos| {
-                    if explored.insert(pos) {
-                        queue.push_back(pos)
-                    }
-                })
-        }
+        };
 
-        explored.len()
+        search::bfs_reach([START_POSITION], neighbors).len()
     }
 }
 
@@ -236,4 +189,45 @@ impl From<HashSet<(u64, u64)>> for Scan {
     }
 }
 
-derive_tests!(Solutions, DAY_14);
\ No newline at end of file
+derive_tests!(Solutions, DAY_14);
+
+fn parse_input(src: Source<'_>) -> ParseResult<Scan> {
+    let parse_line = |line: &&str| {
+        line
+            .split("->")
+            .map(|pair| src.split_once(pair.trim(), ','))
+            .map(|pair| {
+                let (l, r) = pair?;
+                Ok((
+                    src.parse::<u64>(l)?,
+                    src.parse::<u64>(r)?
+                ))
+            })
+            .collect::<ParseResult<Vec<_>>>()
+    };
+
+    let interpolate_line = |line: Vec<(u64, u64)>| {
+        line
+            .windows(2)
+            .flat_map(|w| cartesian_interpolate(&w[0], &w[1]))
+            .collect::<Vec<_>>()
+    };
+    
+    let filled = src.lines()
+        .fold(HashSet::new(), |mut acc, line| {
+            acc.insert(line);
+            acc
+        })
+        .iter()
+        .map(parse_line)
+        .try_fold(HashSet::new(), |mut acc, line| {
+            acc.extend(interpolate_line(line?));
+            Ok(acc)
+        })?;
+
+    if filled.is_empty() {
+        return Err(src.eof("expected at least one rock path"));
+    }
+
+    Ok(Scan::from(filled))
+}
\ No newline at end of file
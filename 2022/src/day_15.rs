@@ -39,55 +39,46 @@ use std::ops::Range;
 
 use super::*;
 
-#[cfg(test)]
-const TARGET_ROW: i64 = 10;
-#[cfg(test)]
-const MAX_COORD: i64 = 20;
-#[cfg(not(test))]
-const TARGET_ROW: i64 = 2_000_000;
-#[cfg(not(test))]
-const MAX_COORD: i64 = 4_000_000;
+const TARGET_ROW: Param<i64> = Param::new(DAY_15, "target_row", 10, 2_000_000);
+const MAX_COORD: Param<i64> = Param::new(DAY_15, "max_coord", 20, 4_000_000);
 
 impl Solution<DAY_15> for Solutions {
     type Input<'i> = Vec<(Point, Point)>;
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        let parse_side = |side: &str| {
-            let filtered = side
-                .chars()
-                .filter(|c| c.is_numeric() || c == &',' || c == &'-')
-                .collect::<String>();
-            
-            let (left, right) = filtered.split_once(',').unwrap();
-
-            (
-                left.parse::<i64>().unwrap(),
-                right.parse::<i64>().unwrap()
-            )
+        let src = Source::new(DAY_15, puzzle);
+
+        let parse_side = |side: &str, prefix: &str| {
+            let side = src.strip_prefix(side.trim(), prefix)?;
+            let (left, right) = src.split_once(side, ", ")?;
+
+            Ok(Point::new(
+                src.parse::<i64>(src.strip_prefix(left, "x=")?)?,
+                src.parse::<i64>(src.strip_prefix(right, "y=")?)?
+            ))
         };
         
-        puzzle
-            .lines()
-            .map(|line| line.split_once(':').unwrap())
-            .map(|(left, right)| (
-                parse_side(left),
-                parse_side(right)
-            ))
-            .map(|(left, right)| (
-                Point::from(left),
-                Point::from(right)
-            ))
-            .collect()
+        src.lines()
+            .map(|line| {
+                let (left, right) = src.split_once(line, ':')?;
+                Ok((
+                    parse_side(left, "Sensor at ")?,
+                    parse_side(right, "closest beacon is at ")?
+                ))
+            })
+            .collect::<ParseResult<_>>()
+            .or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
+        let target_row = TARGET_ROW.get();
         let mut set = Vec::new();
 
         for (sensor, beacon) in input {
             let distance = sensor.manhattan_dist(beacon);
-            let excluded = sensor.exclusion_ranges(distance as u64)
-                .filter(|(i, _)| *i == TARGET_ROW)
+            let excluded = exclusion_ranges(sensor, distance as u64)
+                .filter(|(i, _)| *i == target_row)
                 .map(|(_, r)| r);
 
                 set.extend(excluded);
@@ -100,15 +91,16 @@ impl Solution<DAY_15> for Solutions {
     }
 
     fn part_two(input: &Self::Input<'_>) -> Self::Output {
+        let max_coord = MAX_COORD.get();
         let mut map: Vec<_> = input
             .iter()
             .flat_map(|(sensor, beacon)| {
                 let distance = sensor.manhattan_dist(beacon);
-                sensor.exclusion_ranges(distance as u64)
-                    .filter(|(i, _)| *i >= 0 && *i <= MAX_COORD)
+                exclusion_ranges(sensor, distance as u64)
+                    .filter(|(i, _)| *i >= 0 && *i <= max_coord)
                     .map(|(i, mut range)| {
                         range.start = std::cmp::max(0, range.start);
-                        range.end = std::cmp::min(MAX_COORD + 1, range.end);
+                        range.end = std::cmp::min(max_coord + 1, range.end);
                         (i, range)
                     })
             })
@@ -118,7 +110,7 @@ impl Solution<DAY_15> for Solutions {
             a.0.cmp(&b.0)
         });
 
-        for y in (0..=MAX_COORD).rev() {
+        for y in (0..=max_coord).rev() {
             let count = map
                 .iter()
                 .rev()
@@ -153,57 +145,32 @@ impl Test<DAY_15> for Solutions {
 
 type Exclusion = (i64, Range<i64>);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Point {
-    x: i64,
-    y: i64
-}
-
-impl Point {
-    pub fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-
-    pub fn manhattan_dist(&self, other: &Self) -> i64 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
-    }
-
-    pub fn exclusion_ranges(&self, size: u64) -> impl Iterator<Item=Exclusion> {
-        let start = Point {
-            x: self.x,
-            y: self.y - (size as i64),
-        };
+fn exclusion_ranges(sensor: &Point, size: u64) -> impl Iterator<Item=Exclusion> {
+    let start = sensor.offset(0, -(size as i64));
 
-        self.widths(size)
-            .enumerate()
-            .map(move |(i, width)| {
-                let y = start.y + (i as i64);
-                let x_start = start.x - (width / 2);
-                let x_end = x_start + width;
-
-                (y, x_start..x_end)
-            })
-    }
+    widths(size)
+        .enumerate()
+        .map(move |(i, width)| {
+            let y = start.y + (i as i64);
+            let x_start = start.x - (width / 2);
+            let x_end = x_start + width;
 
-    fn widths(&self, size: u64) -> impl Iterator<Item=i64> {
-        let max_width = (size * 2 + 1) as i64; 
+            (y, x_start..x_end)
+        })
+}
 
-        let top_widths = (1..=max_width - 2).step_by(2);
-        let peak_width = std::iter::once(max_width);
-        let bottom_widths = (1..=max_width - 2)
-            .rev()
-            .step_by(2);
+fn widths(size: u64) -> impl Iterator<Item=i64> {
+    let max_width = (size * 2 + 1) as i64; 
 
-        top_widths
-            .chain(peak_width)
-            .chain(bottom_widths)
-    }
-}
+    let top_widths = (1..=max_width - 2).step_by(2);
+    let peak_width = std::iter::once(max_width);
+    let bottom_widths = (1..=max_width - 2)
+        .rev()
+        .step_by(2);
 
-impl From<(i64, i64)> for Point {
-    fn from((x, y): (i64, i64)) -> Self {
-        Self::new(x, y)
-    }
+    top_widths
+        .chain(peak_width)
+        .chain(bottom_widths)
 }
 
 fn merge_intervals(mut data: Vec<Range<i64>>) -> Vec<Range<i64>> {
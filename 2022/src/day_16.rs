@@ -3,112 +3,55 @@
 //! Puzzle opened late (had an exam in the morning + drove home for Christmas break.)
 //! - P1 completed @ >24h (14280)
 //! - P2 completed @ >24h (11613)
+//! 
+//! ## Parsing
+//! Most valves have no flow and only matter as part of the paths between the others, so parsing
+//! collapses the graph down to the valves with flow (plus the start), with the length of the
+//! shortest path between each pair (see [`Valve::tunnels`].)
+//! 
+//! ## Solutions
+//! Both parts are built on [`Network::best_per_mask`], which gives the most pressure one agent can
+//! release while only opening valves out of each possible set of them. It keeps the best total
+//! for each (valve, minutes left, valves opened) state, working down one minute at a time, so
+//! that paths reaching the same state are only explored further once.
+//! - Part one is just the best one agent can do with every valve available.
+//! - Part two splits the valves between the agents, since there's never a reason for two of them to
+//! open the same one. For two agents that's the best of `best[mask] + best[!mask]` over every mask;
+//! for more, see [`split`].
+//! 
+//! Passing `--param 16.plan=true` also prints the schedule behind each answer (see [`Network::plan`]):
+//! which valve each agent opens in which minute, and the running total of pressure released. It goes
+//! to standard error, so that it stays out of the way of `--format json`/`csv` reports.
 
-use std::collections::{HashMap, VecDeque, HashSet};
+use std::collections::{HashMap, HashSet};
 
 use super::*;
 
 const START_VALVE: &str = "AA";
+/// The most valves with flow there can be; every set of them gets an entry per agent in [`split`].
+const MAX_VALVES: usize = 23;
+
+const MINUTES_ONE: Param<u64> = Param::fixed(DAY_16, "minutes_one", 30);
+const MINUTES_TWO: Param<u64> = Param::fixed(DAY_16, "minutes_two", 26);
+/// How many of us (you, the elephant, ...) open valves in part two.
+const AGENTS_TWO: Param<usize> = Param::fixed(DAY_16, "agents_two", 2);
+/// Whether to print the schedule behind each answer.
+const PLAN: Param<bool> = Param::fixed(DAY_16, "plan", false);
 
 impl Solution<DAY_16> for Solutions {
     type Input<'i> = HashMap<String, Valve>;
     type Output = u64;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        let parse_line = |line: &str| {
-            let (left, right) = line.split_once(';').unwrap();
-            let (valve, flow) = left.split_once('=').unwrap();
-
-            let id = valve
-                .trim_start_matches("Valve ")
-                .trim_end_matches(" has flow rate")
-                .to_owned();
-            
-            let flow = flow.parse::<u64>().unwrap();
-
-            let tunnels: Vec<_> = right
-                .chars()
-                .filter(|c| c.is_uppercase() || c == &',')
-                .collect::<String>()
-                .split(',')
-                .map(str::trim)
-                .map(str::to_owned)
-                .map(|id| (1, id))
-                .collect();
-
-            (id, Valve { flow, tunnels })
-        };
-
-        let graph_base: HashMap<_, _> = puzzle
-            .lines()
-            .map(parse_line)
-            .collect();
-
-        let graph_view: Vec<_> = graph_base
-            .iter()
-            .filter(|(id, v)| v.flow != 0 || id == &START_VALVE)
-            .collect();
-        
-        graph_view
-            .iter()
-            .map(|(outer, valve)| {
-                let tunnels = graph_view
-                    .iter()
-                    .filter(|(inner, _)| inner != outer && inner != &START_VALVE)
-                    .map(|(inner, _)| {
-                        (shortest_path(&graph_base, outer, inner), inner.to_string())
-                    })
-                    .collect();
-
-                (outer.to_string(), Valve {
-                    flow: valve.flow,
-                    tunnels
-                })
-            })
-            .collect()
+        parse_input(Source::new(DAY_16, puzzle)).or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
-        depth_first(input, State {
-            id: START_VALVE,
-            flow_total: 0,
-            time_left: 30,
-            explored: vec![START_VALVE]
-        })
-        .iter()
-        .map(|state| state.flow_total)
-        .max()
-        .unwrap()
+        solve(&Network::new(input), MINUTES_ONE.get(), 1)
     }
 
     fn part_two(input: &Self::Input<'_>) -> Self::Output {
-        let paths: Vec<_> = depth_first(input, State {
-            id: START_VALVE,
-            flow_total: 0,
-            time_left: 26,
-            explored: vec![START_VALVE]
-        })
-        .iter()
-        .cloned()
-        .map(|mut state| {
-            state.explored.remove(0);
-            state
-        })
-        .collect();
-
-        let mut max = 0;
-
-        for outer in &paths {
-            for inner in &paths {
-                if outer.explored.iter().any(|valve| inner.explored.contains(valve)) {
-                    continue;
-                } else {
-                    max = max.max(outer.flow_total + inner.flow_total);
-                }
-            }
-        }
-
-        max
+        solve(&Network::new(input), MINUTES_TWO.get(), AGENTS_TWO.get())
     }
 }
 
@@ -127,66 +70,382 @@ pub struct Valve {
     tunnels: Vec<(usize, String)>
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-pub struct State<'a> {
-    id: &'a str,
-    flow_total: u64,
-    time_left: u64,
-    explored: Vec<&'a str>,
+fn parse_input(src: Source<'_>) -> ParseResult<HashMap<String, Valve>> {
+    let mut valves = Vec::new();
+
+    for line in src.lines() {
+        let (left, right) = src.split_once(line, "; ")?;
+        let (valve, flow) = src.split_once(left, '=')?;
+
+        let id = src
+            .strip_prefix(valve, "Valve ")?
+            .trim_end_matches(" has flow rate");
+        
+        let flow = src.parse::<u64>(flow)?;
+
+        let tunnels = right
+            .strip_prefix("tunnels lead to valves ")
+            .or_else(|| right.strip_prefix("tunnel leads to valve "))
+            .ok_or_else(|| src.error(right, "expected a list of tunnels"))?;
+
+        valves.push((id, flow, tunnels.split(", ").collect::<Vec<_>>()));
+    }
+
+    let ids: HashSet<_> = valves.iter().map(|(id, ..)| *id).collect();
+
+    if !ids.contains(START_VALVE) {
+        return Err(src.eof(format!("there is no valve {START_VALVE}")));
+    }
+
+    for tunnel in valves.iter().flat_map(|(_, _, tunnels)| tunnels) {
+        if !ids.contains(tunnel) {
+            return Err(src.error(tunnel, format!("there is no valve {tunnel}")));
+        }
+    }
+
+    let flowing = valves.iter().filter(|(id, flow, _)| *flow != 0 && *id != START_VALVE).count();
+
+    if flowing > MAX_VALVES {
+        return Err(src.eof(format!("{flowing} valves have flow, but at most {MAX_VALVES} are supported")));
+    }
+
+    let graph_base: HashMap<_, _> = valves
+        .into_iter()
+        .map(|(id, flow, tunnels)| (id.to_owned(), Valve {
+            flow,
+            tunnels: tunnels
+                .into_iter()
+                .map(|id| (1, id.to_owned()))
+                .collect()
+        }))
+        .collect();
+
+    let graph_view: Vec<_> = graph_base
+        .iter()
+        .filter(|(id, v)| v.flow != 0 || id == &START_VALVE)
+        .collect();
+    
+    graph_view
+        .iter()
+        .map(|(outer, valve)| {
+            let tunnels = graph_view
+                .iter()
+                .filter(|(inner, _)| inner != outer && inner != &START_VALVE)
+                .map(|(inner, _)| {
+                    shortest_path(&graph_base, outer, inner)
+                        .map(|dist| (dist, inner.to_string()))
+                        .ok_or_else(|| src.eof(format!("there is no path between {outer} and {inner}")))
+                })
+                .collect::<ParseResult<_>>()?;
+
+            Ok((outer.to_string(), Valve {
+                flow: valve.flow,
+                tunnels
+            }))
+        })
+        .collect()
+}
+
+fn shortest_path(map: &HashMap<String, Valve>, start: &str, end: &str) -> Option<usize> {
+    let neighbors = |id: &&str| {
+        map[*id].tunnels
+            .iter()
+            .map(|(_, edge)| edge.as_str())
+    };
+
+    search::bfs([start], neighbors, |id| *id == end).map(|path| path.cost)
 }
 
-fn shortest_path(map: &HashMap<String, Valve>, start: &str, end: &str) -> usize {
-    let mut stack = VecDeque::new();
-    let mut explored = HashSet::new();
+/// The valves worth opening (those with any flow), compressed so that valve `i` is bit `i` of a mask.
+struct Network<'a> {
+    ids: Vec<&'a str>,
+    flows: Vec<u64>,
+    /// `distances[from][to]`, in minutes; the start valve is index `ids.len()`.
+    distances: Vec<Vec<u64>>,
+}
+
+impl<'a> Network<'a> {
+    fn new(map: &'a HashMap<String, Valve>) -> Self {
+        let mut ids: Vec<&str> = map
+            .iter()
+            .filter(|(id, valve)| valve.flow != 0 && *id != START_VALVE)
+            .map(|(id, _)| id.as_str())
+            .collect();
+
+        ids.sort_unstable();
+
+        let flows = ids.iter().map(|id| map[*id].flow).collect();
+
+        let distances = ids
+            .iter()
+            .chain([&START_VALVE])
+            .map(|from| {
+                let tunnels: HashMap<_, _> = map[*from].tunnels
+                    .iter()
+                    .map(|(dist, to)| (to.as_str(), *dist as u64))
+                    .collect();
+
+                ids.iter().map(|to| tunnels.get(to).copied().unwrap_or(0)).collect()
+            })
+            .collect();
+
+        Self { ids, flows, distances }
+    }
+
+    fn start(&self) -> usize {
+        self.ids.len()
+    }
 
-    explored.insert(start);
-    stack.push_back((0, start));
+    /// Every state one agent can reach in `minutes` while only opening valves in `allowed`.
+    ///
+    /// States are keyed on (minutes left, valve, opened mask), and only the best total pressure
+    /// for each is kept (along with the state it was reached from), working down one minute at a
+    /// time so that paths reaching the same state are only explored further once.
+    fn explore(&self, minutes: u64, allowed: u32) -> HashMap<StateKey, Reached> {
+        let mut layers: Vec<HashMap<(usize, u32), Reached>> = vec![HashMap::new(); minutes as usize + 1];
+        layers[minutes as usize].insert((self.start(), 0), (0, None));
+
+        for left in (0..=minutes).rev() {
+            let (below, rest) = layers.split_at_mut(left as usize);
+
+            for (&(valve, opened), &(total, _)) in &rest[0] {
+                for next in (0..self.ids.len()).filter(|next| (allowed & !opened) & (1 << next) != 0) {
+                    // Walking there and opening it has to leave at least a minute of flow.
+                    let Some(after) = left.checked_sub(self.distances[valve][next] + 1).filter(|after| *after > 0) else {
+                        continue
+                    };
+
+                    let candidate = (total + self.flows[next] * after, Some((left, valve, opened)));
+                    let entry = below[after as usize].entry((next, opened | (1 << next))).or_insert(candidate);
+
+                    if candidate.0 > entry.0 {
+                        *entry = candidate;
+                    }
+                }
+            }
+        }
 
+        layers
+            .into_iter()
+            .enumerate()
+            .flat_map(|(left, layer)| {
+                layer
+                    .into_iter()
+                    .map(move |((valve, opened), best)| ((left as u64, valve, opened), best))
+            })
+            .collect()
+    }
 
-    while let Some((steps, id)) = stack.pop_front() {            
-        if id == end {
-            return steps;
+    /// The most pressure one agent can release in `minutes`, opening only valves in each mask.
+    fn best_per_mask(&self, minutes: u64) -> Vec<u64> {
+        let count = self.ids.len();
+        let mut best = vec![0; 1 << count];
+
+        for ((_, _, opened), (total, _)) in self.explore(minutes, u32::MAX) {
+            best[opened as usize] = best[opened as usize].max(total);
         }
 
-        for edge in &map.get(id).unwrap().tunnels {
-            if explored.insert(edge.1.as_str()) {
-                stack.push_back((steps + 1, edge.1.as_str()))
+        // Allow any subset of each mask, rather than exactly it.
+        for bit in 0..count {
+            for mask in 0..best.len() {
+                if mask & (1 << bit) != 0 {
+                    best[mask] = best[mask].max(best[mask ^ (1 << bit)]);
+                }
             }
         }
-        
+
+        best
     }
 
-    panic!("No path found between {start} and {end}!")
-}
+    /// A schedule for one agent releasing the most pressure in `minutes` while only opening valves in `allowed`.
+    fn plan(&self, minutes: u64, allowed: u32) -> Vec<Step<'a>> {
+        let states = self.explore(minutes, allowed);
 
-fn depth_first<'a>(map: &'a HashMap<String, Valve>, init: State<'a>) -> HashSet<State<'a>> {
-    let mut stack = vec![init];
-    let mut paths = HashSet::new();
-
-    while let Some(state) = stack.pop() {
-        for (dist, id) in &map[state.id].tunnels {
-            let time_left = match state.time_left < (dist + 1) as u64 {
-                false => state.time_left - (dist + 1) as u64,
-                true => continue
-            };
-
-            if time_left > 0 && !state.explored.contains(&id.as_str()) {
-                let mut explored = state.explored.clone();
-                explored.push(id);
-
-                stack.push(State {
-                    id,
-                    flow_total: state.flow_total + (map[id].flow * time_left),
-                    time_left,
-                    explored
-                })
+        let mut state = states
+            .iter()
+            .max_by_key(|(_, (total, _))| *total)
+            .map(|(state, _)| *state);
+
+        let mut steps = Vec::new();
+
+        // Walk back from the best final state; every state but the first was reached by opening its valve.
+        while let Some(key @ (left, valve, _)) = state {
+            let (_, parent) = states[&key];
+
+            if parent.is_some() {
+                steps.push(Step {
+                    minute: minutes - left,
+                    valve: self.ids[valve],
+                    released: self.flows[valve] * left,
+                });
             }
+
+            state = parent;
         }
 
-        paths.insert(state);
+        steps.reverse();
+        steps
+    }
+}
+
+/// A state of the search: (minutes left, valve, opened mask.)
+type StateKey = (u64, usize, u32);
+/// The best total pressure found for a state, and the state it was reached from.
+type Reached = (u64, Option<StateKey>);
+
+/// Opening a valve, as part of a [plan](Network::plan).
+#[derive(Debug, Clone, Copy)]
+struct Step<'a> {
+    /// The minute during which it's opened.
+    minute: u64,
+    valve: &'a str,
+    /// How much pressure it releases from then on.
+    released: u64,
+}
+
+/// Every submask of `mask`, from itself down to zero.
+fn submasks(mask: usize) -> impl Iterator<Item = usize> {
+    std::iter::successors(Some(mask), move |sub| (*sub != 0).then(|| (sub - 1) & mask))
+}
+
+/// The best way to split the valves between `agents` agents, as the set each one opens valves from,
+/// given the best one agent can do with each set of valves.
+///
+/// Agents never need to open the same valve, so the best they can do together is the best of
+/// these splits. Splitting between two agents means trying every mask and its complement; for
+/// more, each extra agent takes a pass over every submask of every mask (3^n in total.)
+fn split(best: &[u64], agents: usize) -> Vec<usize> {
+    let all = best.len() - 1;
+
+    if agents == 0 {
+        return Vec::new();
+    }
+
+    // teams[k][mask] is the best that k + 1 agents can do with the valves in mask.
+    let mut teams = vec![best.to_vec()];
+
+    for _ in 2..agents {
+        let last = teams.last().unwrap();
+
+        let next = (0..=all)
+            .map(|mask| submasks(mask).map(|own| last[mask ^ own] + best[own]).max().unwrap_or(0))
+            .collect();
+
+        teams.push(next);
+    }
+
+    // Peel off one agent's share at a time, leaving the rest to the others.
+    let mut masks = Vec::with_capacity(agents);
+    let mut remaining = all;
+
+    for team in teams.iter().rev().take(agents - 1) {
+        let own = submasks(remaining)
+            .max_by_key(|own| team[remaining ^ own] + best[*own])
+            .unwrap();
+
+        masks.push(own);
+        remaining ^= own;
+    }
+
+    masks.push(remaining);
+    masks
+}
+
+/// The most pressure `agents` agents can release in `minutes`, printing their schedule (to standard error) if [`PLAN`] is set.
+fn solve(network: &Network<'_>, minutes: u64, agents: usize) -> u64 {
+    let best = network.best_per_mask(minutes);
+    let masks = split(&best, agents);
+
+    if PLAN.get() {
+        let plans: Vec<_> = masks
+            .iter()
+            .map(|mask| network.plan(minutes, *mask as u32))
+            .collect();
+
+        eprint!("{}", schedule(minutes, &plans));
+    }
+
+    masks.iter().map(|mask| best[*mask]).sum()
+}
+
+/// Lay out the agents' plans as one timeline, with the running total of pressure released.
+fn schedule(minutes: u64, plans: &[Vec<Step<'_>>]) -> String {
+    let mut steps: Vec<_> = plans
+        .iter()
+        .enumerate()
+        .flat_map(|(agent, plan)| plan.iter().map(move |step| (agent + 1, step)))
+        .collect();
+
+    steps.sort_by_key(|(agent, step)| (step.minute, *agent));
+
+    let mut out = format!("Plan for {} agent(s) over {minutes} minutes:\n", plans.len());
+    let mut total = 0;
+
+    for (agent, step) in steps {
+        total += step.released;
+
+        out += &format!(
+            "  minute {:>2}: agent {agent} opens {} (releasing {} over {} minutes; {total} in total)\n",
+            step.minute, step.valve, step.released, minutes - step.minute
+        );
     }
 
-    paths
+    out
 }
 
-derive_tests!(Solutions, DAY_16);
\ No newline at end of file
+derive_tests!(Solutions, DAY_16);
+
+#[cfg(test)]
+mod network {
+    use super::*;
+
+    fn example() -> HashMap<String, Valve> {
+        parse_input(Source::new(DAY_16, include_str!("inputs/test_16.txt"))).unwrap()
+    }
+
+    #[test]
+    fn agents() {
+        let map = example();
+        let best = Network::new(&map).best_per_mask(26);
+
+        let totals: Vec<u64> = (0..=4)
+            .map(|agents| split(&best, agents).iter().map(|mask| best[*mask]).sum())
+            .collect();
+
+        assert_eq!(totals, [0, 1327, 1707, 1794, 1825]);
+    }
+
+    #[test]
+    fn plans() {
+        let map = example();
+        let network = Network::new(&map);
+
+        let plan = network.plan(30, u32::MAX);
+        assert!(plan.windows(2).all(|pair| pair[0].minute < pair[1].minute));
+        assert_eq!(plan.iter().map(|step| step.released).sum::<u64>(), 1651);
+
+        let best = network.best_per_mask(26);
+        let plans: Vec<_> = split(&best, 2).iter().map(|mask| network.plan(26, *mask as u32)).collect();
+        assert_eq!(plans.iter().flatten().map(|step| step.released).sum::<u64>(), 1707);
+
+        let schedule = schedule(26, &plans);
+        let lines: Vec<_> = schedule.lines().collect();
+
+        assert_eq!(lines[0], "Plan for 2 agent(s) over 26 minutes:");
+        assert_eq!(lines.len(), 7);
+        assert!(lines[1..].is_sorted_by_key(|line| line[9..11].trim().parse::<u64>().unwrap()));
+        assert!(lines[6].ends_with("; 1707 in total)"));
+    }
+
+    #[test]
+    fn too_many_valves() {
+        let mut puzzle = String::from("Valve AA has flow rate=0; tunnels lead to valves B0, B23\n");
+
+        for i in 0..24 {
+            puzzle += &format!("Valve B{i} has flow rate=1; tunnels lead to valves B{}, B{}\n", (i + 23) % 24, (i + 1) % 24);
+        }
+
+        let err = parse_input(Source::new(DAY_16, &puzzle)).unwrap_err();
+        assert_eq!(err.message, "24 valves have flow, but at most 23 are supported");
+    }
+}
\ No newline at end of file
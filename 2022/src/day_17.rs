@@ -1,117 +1,69 @@
-//! # Day 17 - 
+//! # Day 17 - Pyroclastic Flow
 //! 
 //! Puzzle opened late - I unfortunately got sick, so I'm likely going to be behind for the rest of the season :(
 //! - P1 completed @ > 24h
 //! - P2 completed @ > 24h
+//! 
+//! ## Solutions
+//! - Part one just simulates the rocks falling; see [`Tower::drop_rock`].
+//! - Part two asks for the height after a trillion rocks, which is far too many to simulate. Instead, we
+//! fingerprint the state after each rock (which rock comes next, which jet comes next, and how far down
+//! the top of each column is) and hand them to [`cycle::find`]. Once a fingerprint repeats, the tower
+//! grows by the same amount every period, so the height after any number of rocks can be extrapolated.
 
 use super::*;
 
+const ROCKS_ONE: Param<usize> = Param::fixed(DAY_17, "rocks_one", 2022);
+const ROCKS_TWO: Param<usize> = Param::fixed(DAY_17, "rocks_two", 1_000_000_000_000);
+
 impl Solution<DAY_17> for Solutions {
     type Input<'i> = Vec<Jet>;
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        puzzle
-            .chars()
-            .filter(|c| c == &'<' || c == &'>')
-            .map(Jet::from)
-            .collect()
+        let src = Source::new(DAY_17, puzzle);
+
+        match src.chars(puzzle.trim_end(), Jet::from_char) {
+            Ok(jets) if jets.is_empty() => src.eof("expected at least one jet").raise(),
+            jets => jets.or_raise()
+        }
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
         let mut tower = Tower::new();
         let mut rocks = Rock::generator();
-        let mut jets = input.iter().cycle();
-
-        for _ in 0..2022 {
-            let mut rock = rocks.next().unwrap();
-            rock.shift_unchecked((tower.max + 3) as isize, 0);
-
-            let ceiling = rock.coords
-                .iter()
-                .map(|(row, _)| row)
-                .max()
-                .unwrap();
-            
-            tower.raise_ceiling(*ceiling);
-            
-            loop {
-                match jets.next().unwrap() {
-                    Jet::Left => rock.shift_checked(&tower, 0, -1),
-                    Jet::Right => rock.shift_checked(&tower, 0, 1)
-                };
-
-                if !rock.shift_checked(&tower, -1, 0) {
-                    break;
-                }
-            }
+        let mut jet = 0;
 
-            for (row, col) in rock.coords {
-                tower.mark_occupied(row, col)
-            }
+        for _ in 0..ROCKS_ONE.get() {
+            tower.drop_rock(rocks.next().unwrap(), input, &mut jet);
         }
 
         tower.max
     }
 
     fn part_two(input: &Self::Input<'_>) -> Self::Output {
-        const STEP_COUNT: usize = 1_000_000_000_000;
+        let rock_count = ROCKS_TWO.get();
 
-        let mut tower = Tower::new();
-        let mut rocks = Rock::generator();
-        let mut jets = input.iter().enumerate().cycle();
-
-        let mut cache = Vec::new();
-        let mut loop_points = Vec::new();
-
-        for i in 0.. {
-            let mut rock = rocks.next().unwrap();
-            rock.shift_unchecked((tower.max + 3) as isize, 0);
-
-            let ceiling = rock.coords
-                .iter()
-                .map(|(row, _)| row)
-                .max()
-                .unwrap();
-            
-            tower.raise_ceiling(*ceiling);
-            
-            loop {
-                let (idx, jet) = jets.next().unwrap();
-
-                if idx % 10091 == 0 {
-                    loop_points.push((i, tower.max));
-                }
-
-                match jet {
-                    Jet::Left => rock.shift_checked(&tower, 0, -1),
-                    Jet::Right => rock.shift_checked(&tower, 0, 1)
-                };
-
-                if !rock.shift_checked(&tower, -1, 0) {
-                    break;
-                }
-            }
-
-            for (row, col) in rock.coords {
-                tower.mark_occupied(row, col)
-            }
+        if rock_count == 0 {
+            return 0;
+        }
 
-            cache.push(tower.max);
+        let mut tower = Tower::new();
+        let mut rocks = Rock::generator().enumerate();
+        let mut jet = 0;
 
-            if loop_points.len() >= 3 {
-                break;
-            }
-        }
+        // After each rock, what happens next only depends on the next rock, the next jet
+        // and the shape of the top of the tower - so once those repeat, so does the growth.
+        let states = std::iter::from_fn(|| {
+            let (i, rock) = rocks.next().unwrap();
+            tower.drop_rock(rock, input, &mut jet);
 
-        // None of this should work but it does???
-        let rocks_per = loop_points[2].0 - loop_points[1].0;
-        let height_per = loop_points[2].1 - loop_points[1].1;
+            Some(((i % 5, jet, tower.surface()), tower.max))
+        });
 
-        let remainder_steps = STEP_COUNT % rocks_per;
-        let steps = (STEP_COUNT - remainder_steps) / rocks_per;
-        
-        cache[remainder_steps] + (height_per * steps - 1)
+        cycle::find(states)
+            .expect("The rocks never fell into a cycle.")
+            .value(rock_count - 1)
     }
 }
 
@@ -119,8 +71,7 @@ impl Test<DAY_17> for Solutions {
     fn expected(part: bool) -> Self::Output {
         match part {
             PART_ONE => 3068,
-            // Not the actual correct answer but :shrug:
-            PART_TWO => 1333333333336
+            PART_TWO => 1514285714288
         }
     }
 }
@@ -131,12 +82,12 @@ pub enum Jet {
     Right,
 }
 
-impl From<char> for Jet {
-    fn from(value: char) -> Self {
+impl Jet {
+    fn from_char(value: char) -> Option<Self> {
         match value {
-            '<' => Jet::Left,
-            '>' => Jet::Right,
-            _ => unreachable!()
+            '<' => Some(Jet::Left),
+            '>' => Some(Jet::Right),
+            _ => None
         }
     }
 }
@@ -259,6 +210,47 @@ impl Tower {
         self.buffer[row][col] = true;
         self.max = std::cmp::max(self.max, row + 1);
     }
+
+    /// Drop a rock until it comes to rest, pushing it with the jets from `jets[*jet]` onwards.
+    pub fn drop_rock(&mut self, mut rock: Rock, jets: &[Jet], jet: &mut usize) {
+        rock.shift_unchecked((self.max + 3) as isize, 0);
+
+        let ceiling = rock.coords
+            .iter()
+            .map(|(row, _)| row)
+            .max()
+            .unwrap();
+
+        self.raise_ceiling(*ceiling);
+
+        loop {
+            match jets[*jet] {
+                Jet::Left => rock.shift_checked(self, 0, -1),
+                Jet::Right => rock.shift_checked(self, 0, 1)
+            };
+
+            *jet = (*jet + 1) % jets.len();
+
+            if !rock.shift_checked(self, -1, 0) {
+                break;
+            }
+        }
+
+        for (row, col) in rock.coords {
+            self.mark_occupied(row, col)
+        }
+    }
+
+    /// How far below the top of the tower the highest rock in each column is.
+    pub fn surface(&self) -> [usize; 7] {
+        std::array::from_fn(|col| {
+            self.buffer[..self.max]
+                .iter()
+                .rev()
+                .position(|row| row[col])
+                .unwrap_or(self.max)
+        })
+    }
 }
 
 impl std::fmt::Display for Tower {
@@ -7,30 +7,7 @@ impl Solution<DAY_01> for Solutions {
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        let mut l = vec![];
-        let mut r = vec![];
-
-        let usize_parse = |(a, b): (&str, &str)| {
-            (
-                a.parse::<usize>().unwrap(),
-                b.parse::<usize>().unwrap()
-            )
-        };
-
-        for line in puzzle.lines() {
-            let (left, right) = line
-                .split_once("   ")
-                .map(usize_parse)
-                .unwrap();
-            
-            l.push(left);
-            r.push(right);
-        }
-
-        l.sort_unstable();
-        r.sort_unstable();
-        
-        (l, r)
+        parse_input(Source::new(DAY_01, puzzle)).or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
@@ -70,4 +47,21 @@ impl Test<DAY_01> for Solutions {
     }
 }
 
-derive_tests!(Solutions, DAY_01);
\ No newline at end of file
+derive_tests!(Solutions, DAY_01);
+
+fn parse_input(src: Source<'_>) -> ParseResult<(Vec<usize>, Vec<usize>)> {
+    let mut l = vec![];
+    let mut r = vec![];
+
+    for line in src.lines() {
+        let (left, right) = src.split_once(line, "   ")?;
+        
+        l.push(src.parse::<usize>(left)?);
+        r.push(src.parse::<usize>(right)?);
+    }
+
+    l.sort_unstable();
+    r.sort_unstable();
+    
+    Ok((l, r))
+}
\ No newline at end of file
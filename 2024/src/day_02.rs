@@ -10,19 +10,20 @@ impl Solution<DAY_02> for Solutions {
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
+        let src = Source::new(DAY_02, puzzle);
+
         let parse_line = |l: &str| {
             l
                 .split(' ')
-                .map(str::parse::<isize>)
-                .map(Result::unwrap)
-                .collect()
+                .map(|v| src.parse::<isize>(v))
+                .collect::<ParseResult<Vec<_>>>()
         };
 
-        puzzle
-            .lines()
+        src.lines()
             .map(parse_line)
-            .map(|v| Report { values: v })
-            .collect()
+            .map(|v| Ok(Report { values: v? }))
+            .collect::<ParseResult<_>>()
+            .or_raise()
     }
 
     fn part_one(input: &Self::Input<'_>) -> Self::Output {
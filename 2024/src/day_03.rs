@@ -17,7 +17,7 @@ impl Solution<DAY_03> for Solutions {
                 .take_while(|c| c.is_ascii_digit() || *c == ',')
                 .count();
 
-            s.chars().nth(count).unwrap() == ')'
+            s.chars().nth(count) == Some(')')
         };
         
         let parse_numbers = |s: &str| {
@@ -26,12 +26,13 @@ impl Solution<DAY_03> for Solutions {
                 .take_while(|c| *c != ')')
                 .collect();
 
-            let (l, r) = s.split_once(',').unwrap();
+            // Corrupted memory, so anything that doesn't fit the pattern is simply skipped.
+            let (l, r) = s.split_once(',')?;
 
-            Instruction::Multiply(
-                l.parse::<usize>().unwrap(),
-                r.parse::<usize>().unwrap()
-            )
+            Some(Instruction::Multiply(
+                l.parse::<usize>().ok()?,
+                r.parse::<usize>().ok()?
+            ))
         };
 
         let mut instructions = vec![];
@@ -55,8 +56,8 @@ impl Solution<DAY_03> for Solutions {
             if let Some(s) = mul_slice {
                 if s == "mul(" {
                     let rest = &puzzle[i + 4..];
-                    if filter_candidate(rest) {
-                        instructions.push(parse_numbers(rest));
+                    if let Some(mul) = Some(rest).filter(|s| filter_candidate(s)).and_then(parse_numbers) {
+                        instructions.push(mul);
                         continue;
                     }
                 }
@@ -99,15 +100,4 @@ impl Solution<DAY_03> for Solutions {
 
         product
     }
-}
-
-impl Test<DAY_03> for Solutions {
-    fn expected(part: bool) -> Self::Output {
-        match part {
-            PART_ONE => 161,
-            PART_TWO => 48,
-        }
-    }
-}
-
-derive_tests!(Solutions, DAY_03);
\ No newline at end of file
+}
\ No newline at end of file
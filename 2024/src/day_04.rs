@@ -1,82 +1,34 @@
 use super::*;
 
-#[derive(Debug, Clone)]
-pub struct Grid<T: Clone> {
-    data: Box<[T]>,
-    rows: usize,
-    cols: usize,
-}
-
-impl<T: Clone> Grid<T> {
-    pub fn from_square(data: &[T], dim: usize) -> Self {
-        Self {
-            data: data.to_vec().into_boxed_slice(),
-            rows: dim,
-            cols: dim,
-        }
-    }
-
-    pub fn row(&self, index: usize) -> impl Iterator<Item = &T> {
-        self
-            .data
-            .iter()
-            .skip(index * self.cols)
-            .take(self.cols)
-    }
-
-    pub fn col(&self, index: usize) -> impl Iterator<Item = &T> {
-        self
-            .data
-            .iter()
-            .skip(index)
-            .step_by(self.cols)
-            .take(self.rows)
-    }
-
-    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
-        if row >= self.rows || col >= self.cols {
-            return None;
-        }
-        
-        self.data.get(self.rows * row + col)
-    }
-
-    pub fn dimensions(&self) -> (usize, usize) {
-        (self.rows, self.cols)
-    }
-}
-
 impl Solution<DAY_04> for Solutions {
     type Input<'i> = Grid<char>;
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        let data: Vec<_> = puzzle
-            .lines()
-            .flat_map(str::chars)
-            .collect();
-
-        Grid::from_square(
-            &data,
-            puzzle.lines().count()
-        )
+        Source::new(DAY_04, puzzle)
+            .grid(Some)
+            .or_raise()
     }
 
     fn part_one(input: &Grid<char>) -> Self::Output {
-        let (rows, _) = input.dimensions();
+        let (rows, cols) = input.dimensions();
         let mut count = 0;
 
         for i in 0..rows {
             let r: String = input.row(i).collect();
-            let c: String = input.col(i).collect();
 
             count += r.matches("XMAS").count();
             count += r.matches("SAMX").count();
+        }
+
+        for i in 0..cols {
+            let c: String = input.col(i).collect();
+
             count += c.matches("XMAS").count();
             count += c.matches("SAMX").count();
         }
 
-        for k in 0..rows * 2 {
+        for k in 0..rows + cols {
             let mut s = String::new();
 
             for j in 0..=k {
@@ -93,11 +45,11 @@ impl Solution<DAY_04> for Solutions {
 
         let mut rev = input.clone();
 
-        for i in 0..rev.rows {
-            rev.data[rev.rows * i..(rev.rows * i) + rev.cols].reverse();
+        for i in 0..rows {
+            rev.row_mut(i).reverse();
         }
 
-        for k in 0..rows * 2 {
+        for k in 0..rows + cols {
             let mut s = String::new();
 
             for j in 0..=k {
@@ -116,10 +68,11 @@ impl Solution<DAY_04> for Solutions {
     }
 
     fn part_two(input: &Grid<char>) -> Self::Output {
+        let (rows, cols) = input.dimensions();
         let mut count = 0;
 
-        for r in 0..input.rows - 2 {
-            for c in 0..input.cols - 2 {
+        for r in 0..rows - 2 {
+            for c in 0..cols - 2 {
                 let a = [
                     (r, c),
                     (r + 1, c + 1),
@@ -13,66 +13,7 @@ impl Solution<DAY_05> for Solutions {
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        let (rules, pages) = puzzle
-            .split_once("\n\n")
-            .unwrap();
-
-        let mut r = vec![];
-        let mut p = vec![];
-
-        for rule in rules.lines() {
-            let (a, b) = rule
-                .split_once("|")
-                .unwrap();
-
-            r.push(
-                (a.parse::<usize>().unwrap(), b.parse::<usize>().unwrap())
-            )
-        }
-
-        for numbers in pages.lines() {
-            let v: Vec<_> = numbers
-                .split(",")
-                .map(str::parse::<usize>)
-                .map(Result::unwrap)
-                .collect();
-
-            p.push(v);
-        }
-
-        let mut map = HashMap::new();
-
-        let numbers: HashSet<_> = r
-            .iter()
-            .copied()
-            .flat_map(|(a, b)| [a, b])
-            .collect();
-
-        for n in numbers {
-            let before: HashSet<_> = r
-                .iter()
-                .filter(|(_, r)| *r == n)
-                .map(|(l, _)| l)
-                .copied()
-                .collect();
-
-            let after: HashSet<_> = r
-                .iter()
-                .filter(|(l, _)| *l == n)
-                .map(|(_, r)| r)
-                .copied()
-                .collect();
-
-            map.insert(
-                n,
-                (before, after)
-            );
-        }
-
-        Input {
-            rules: map,
-            pages: p,
-        }
+        parse_input(Source::new(DAY_05, puzzle)).or_raise()
     }
 
     fn part_one(input: &Input) -> Self::Output {
@@ -137,4 +78,62 @@ impl Test<DAY_05> for Solutions {
     }
 }
 
-derive_tests!(Solutions, DAY_05);
\ No newline at end of file
+derive_tests!(Solutions, DAY_05);
+
+fn parse_input(src: Source<'_>) -> ParseResult<Input> {
+    let (rules, pages) = src.split_once(src.puzzle(), "\n\n")?;
+
+    let mut r = vec![];
+    let mut p = vec![];
+
+    for rule in rules.lines() {
+        let (a, b) = src.split_once(rule, '|')?;
+
+        r.push(
+            (src.parse::<usize>(a)?, src.parse::<usize>(b)?)
+        )
+    }
+
+    for numbers in pages.lines() {
+        let v = numbers
+            .split(",")
+            .map(|n| src.parse::<usize>(n))
+            .collect::<ParseResult<Vec<_>>>()?;
+
+        p.push(v);
+    }
+
+    let mut map = HashMap::new();
+
+    let numbers: HashSet<_> = r
+        .iter()
+        .copied()
+        .flat_map(|(a, b)| [a, b])
+        .collect();
+
+    for n in numbers {
+        let before: HashSet<_> = r
+            .iter()
+            .filter(|(_, r)| *r == n)
+            .map(|(l, _)| l)
+            .copied()
+            .collect();
+
+        let after: HashSet<_> = r
+            .iter()
+            .filter(|(l, _)| *l == n)
+            .map(|(_, r)| r)
+            .copied()
+            .collect();
+
+        map.insert(
+            n,
+            (before, after)
+        );
+    }
+
+    Ok(Input {
+        rules: map,
+        pages: p,
+    })
+}
\ No newline at end of file
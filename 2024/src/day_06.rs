@@ -14,153 +14,123 @@ impl Solution<DAY_06> for Solutions {
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-        let data: Vec<_> = puzzle
-            .chars()
-            .filter(|c| *c != '\n')
-            .map(|c| match c {
-                '.' => Cell::Empty,
-                '#' => Cell::Filled,
-                '^' => Cell::Guard,
-                _ => unreachable!()
+        let src = Source::new(DAY_06, puzzle);
+
+        let grid = src
+            .grid(|c| match c {
+                '.' => Some(Cell::Empty),
+                '#' => Some(Cell::Filled),
+                '^' => Some(Cell::Guard),
+                _ => None
             })
-            .collect();
+            .or_raise();
 
-        Grid::from_square(
-            &data,
-            puzzle.lines().count()
-        )
+        if grid.iter().filter(|(_, cell)| **cell == Cell::Guard).count() != 1 {
+            src.eof("expected exactly one guard").raise();
+        }
+
+        grid
     }
 
     fn part_one(input: &Grid<Cell>) -> Self::Output {
-        let (mut r, mut c) = input
-            .coordinates()
-            .find(|(r, c)| input.get(*r, *c).unwrap() == &Cell::Guard)
-            .map(|(r, c)| (r as isize, c as isize))
-            .unwrap();
-
-        let (mut rowv, mut colv) = (-1_isize, 0_isize);
-        
-        let mut set = HashSet::new();
-
-        while (r >= 0 && c >= 0) && (r < input.rows as isize && c < input.cols as isize) {
-            set.insert((r, c));
-
-            let check_next = {
-                let n_r = r + rowv;
-                let n_c = c + colv;
-                
-                #[allow(clippy::match_like_matches_macro)]
-                if let Some(&Cell::Filled) = input.get(n_r as usize, n_c as usize) {
-                    true
-                }
-                else {
-                    false
-                }
-            };
-    
-            if check_next {
-                match (rowv, colv) {
-                    (1, 0) => {
-                        rowv = 0;
-                        colv = -1;
-                    },
-                    (0, 1) => {
-                        rowv = 1;
-                        colv = 0;
-                    },
-                    (-1, 0) => {
-                        rowv = 0;
-                        colv = 1;
-                    },
-                    (0, -1) => {
-                        rowv = -1;
-                        colv = 0;
-                    },
-                    _ => unreachable!()
+        let mut visited = HashSet::new();
+
+        #[cfg(feature = "viz")]
+        let mut recorder = aoc_common::viz::Recorder::for_day(2024, 6).scale(4).every(25);
+
+        for (position, _) in patrol(input, start(input), None) {
+            visited.insert(position);
+
+            #[cfg(feature = "viz")]
+            recorder.frame(format_args!("{} visited", visited.len()), input.dimensions().0, input.dimensions().1, |row, col| {
+                use aoc_common::viz::{BLACK, GREY, RED, YELLOW};
+
+                match input[(row, col)] {
+                    _ if (row, col) == position => RED,
+                    Cell::Filled => GREY,
+                    _ if visited.contains(&(row, col)) => YELLOW,
+                    _ => BLACK
                 }
-            } else {
-                r += rowv;
-                c += colv;
-            }
+            });
         }
 
-        set.len()
+        visited.len()
     }
 
     fn part_two(input: &Grid<Cell>) -> Self::Output {
-        let mut cycles = 0;
-
-        for (a, b) in input
-            .coordinates()
-            .filter(|(r, c)| {
-                let v = input.get(*r, *c).unwrap();
-                v == &Cell::Empty
-            }) 
-        {
-            let mut input = input.clone();
-
-            input.set(a, b, Cell::Filled);
-
-            let (mut r, mut c) = input
-                .coordinates()
-                .find(|(r, c)| input.get(*r, *c).unwrap() == &Cell::Guard)
-                .map(|(r, c)| (r as isize, c as isize))
-                .unwrap();
-
-            let (mut rowv, mut colv) = (-1_isize, 0_isize);
-            let mut iter_count = 0;
-            
-            while (r >= 0 && c >= 0) && (r < input.rows as isize && c < input.cols as isize) {
-                iter_count += 1;
-
-                // deeply fucking evil solution
-                if iter_count > 10_000 {
-                    cycles += 1;
-                    break;
-                }
+        let start = start(input);
+        let (rows, cols) = input.dimensions();
+
+        let mut tried = HashSet::from([start.0]);
+        let mut turns = vec![0_u8; rows * cols];
+        let mut previous = start;
+        let mut loops = 0;
+
+        // An obstacle off the original path would never be walked into, so only those on it are
+        // worth trying. The path up to the first step onto a cell is unaffected by blocking that
+        // cell, so each walk can pick up from just before it.
+        for state in patrol(input, start, None).skip(1) {
+            if state.0 != previous.0 && tried.insert(state.0) && loops_back(input, previous, state.0, &mut turns) {
+                loops += 1;
+            }
 
-                let check_next = {
-                    let n_r = r + rowv;
-                    let n_c = c + colv;
-                    
-                    #[allow(clippy::match_like_matches_macro)]
-                    if let Some(&Cell::Filled) = input.get(n_r as usize, n_c as usize) {
-                        true
-                    }
-                    else {
-                        false
-                    }
-                };
-        
-                if check_next {
-                    match (rowv, colv) {
-                        (1, 0) => {
-                            rowv = 0;
-                            colv = -1;
-                        },
-                        (0, 1) => {
-                            rowv = 1;
-                            colv = 0;
-                        },
-                        (-1, 0) => {
-                            rowv = 0;
-                            colv = 1;
-                        },
-                        (0, -1) => {
-                            rowv = -1;
-                            colv = 0;
-                        },
-                        _ => unreachable!()
-                    }
-                } else {
-                    r += rowv;
-                    c += colv;
-                }
+            previous = state;
+        }
+
+        loops
+    }
+}
+
+/// Where the guard is and which way they're facing.
+type Guard = ((usize, usize), Direction);
+
+/// Where the guard starts out (always facing up.)
+fn start(grid: &Grid<Cell>) -> Guard {
+    let position = grid
+        .position(|cell| *cell == Cell::Guard)
+        .unwrap();
+
+    (position, Direction::Up)
+}
+
+/// The states the guard goes through from `from` until they leave the map (if they ever do),
+/// with an extra obstacle at `obstacle`. Turning in place counts as a state of its own.
+fn patrol(grid: &Grid<Cell>, from: Guard, obstacle: Option<(usize, usize)>) -> impl Iterator<Item = Guard> + '_ {
+    std::iter::successors(Some(from), move |&(position, heading)| {
+        let next = grid.step(position, heading)?;
+
+        match grid[next] == Cell::Filled || Some(next) == obstacle {
+            true => Some((position, heading.turn_right())),
+            false => Some((next, heading))
+        }
+    })
+}
+
+/// Whether the guard gets stuck in a loop from `from` once `obstacle` is placed.
+///
+/// A loop has to pass through some turn twice, so only turns are remembered: `turns` holds the
+/// headings the guard has turned to on each cell, as bits.
+fn loops_back(grid: &Grid<Cell>, from: Guard, obstacle: (usize, usize), turns: &mut [u8]) -> bool {
+    let cols = grid.dimensions().1;
+    let mut position = from.0;
+
+    turns.fill(0);
+
+    for (next, heading) in patrol(grid, from, Some(obstacle)).skip(1) {
+        if next == position {
+            let (cell, bit) = (next.0 * cols + next.1, 1 << heading as u8);
+
+            if turns[cell] & bit != 0 {
+                return true;
             }
+
+            turns[cell] |= bit;
         }
 
-        cycles
+        position = next;
     }
+
+    false
 }
 
 impl Test<DAY_06> for Solutions {
@@ -172,4 +142,38 @@ impl Test<DAY_06> for Solutions {
     }
 }
 
-derive_tests!(Solutions, DAY_06);
\ No newline at end of file
+derive_tests!(Solutions, DAY_06);
+
+#[cfg(test)]
+mod loops {
+    use super::*;
+
+    fn solve(puzzle: &str) -> (usize, usize) {
+        // Like the derived tests, so that any frames recorded stay out of the source tree.
+        aoc_common::params::set_mode(aoc_common::params::Mode::Example);
+
+        let grid = <Solutions as Solution<DAY_06>>::parse(puzzle);
+
+        (<Solutions as Solution<DAY_06>>::part_one(&grid), <Solutions as Solution<DAY_06>>::part_two(&grid))
+    }
+
+    #[test]
+    fn long_patrol() {
+        // Walking straight out takes more steps than the old cutoff, and an obstacle anywhere
+        // just turns the guard off the side of the map.
+        let puzzle = format!("{}^\n", ".\n".repeat(10_001));
+
+        assert_eq!(solve(&puzzle), (10_002, 0));
+    }
+
+    #[test]
+    fn turning_in_place() {
+        // Blocking the only way out leaves the guard spinning on the spot.
+        let puzzle = ".#.\n.^#\n.#.\n";
+        let grid = <Solutions as Solution<DAY_06>>::parse(puzzle);
+        let mut turns = vec![0; 9];
+
+        assert!(loops_back(&grid, start(&grid), (1, 0), &mut turns));
+        assert_eq!(solve(puzzle), (2, 1));
+    }
+}
@@ -5,7 +5,7 @@ impl Solution<DAY_N> for Solutions {
     type Output = usize;
 
     fn parse(puzzle: &str) -> Self::Input<'_> {
-
+        parse_input(Source::new(DAY_N, puzzle)).or_raise()
     }
 }
 
@@ -18,4 +18,12 @@ impl Test<DAY_N> for Solutions {
     }
 }
 
-derive_tests!(Solutions, DAY_N);
\ No newline at end of file
+derive_tests!(Solutions, DAY_N);
+
+fn parse_input(src: Source<'_>) -> ParseResult<()> {
+    for _line in src.lines() {
+
+    }
+
+    Ok(())
+}
\ No newline at end of file
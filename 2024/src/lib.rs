@@ -0,0 +1,34 @@
+#![warn(clippy::perf, clippy::style, warnings)]
+
+//! Solutions for Advent of Code 2024, registered as [`YEAR`] for the `advent_of_code` runner.
+
+use lib_aoc::prelude::*;
+use aoc_common::{
+    prelude::*,
+    runner::{self, Year}
+};
+
+struct Solutions {}
+
+impl Solver for Solutions {
+    fn load(day: u8) -> String {
+        runner::load(&YEAR, day)
+    }
+
+    fn load_test(day: u8, part: bool) -> String {
+        runner::load_test(&YEAR, day, part)
+    }
+}
+
+pub static YEAR: Year = Year {
+    year: 2024,
+    root: env!("CARGO_MANIFEST_DIR"),
+    days: &DAYS,
+    load: Solutions::load,
+    load_test: Solutions::load_test,
+    answers: "src/answers.txt",
+};
+
+// Declares a module for every src/day_NN.rs, and registers them all in `DAYS`
+// (followed by the tests for the multi-case example files.)
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
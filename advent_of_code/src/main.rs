@@ -0,0 +1,22 @@
+#![warn(clippy::perf, clippy::style, warnings)]
+
+//! The runner for every year, e.g. `cargo run --release -- --year 2022 --day 15`.
+
+use aoc_common::runner::{self, Year};
+
+static YEARS: [&Year; 2] = [&aoc_2022::YEAR, &aoc_2024::YEAR];
+
+fn main() {
+    runner::main(&YEARS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn readme_is_up_to_date() {
+        let (_, readme, updated) = aoc_common::stats::readme(&YEARS).unwrap();
+        assert!(readme == updated, "The table in README.md is out of date; regenerate it with `cargo run -- stats --readme`.");
+    }
+}
@@ -0,0 +1,284 @@
+//! Verified answers for the real inputs, and regression checking against them.
+//!
+//! Each year keeps an answers file with one line per accepted answer:
+//! ``` text
+//! # Comments and blank lines are ignored.
+//! 01.1: 66306
+//! 01.2: 195292
+//! ```
+//! Answers that span multiple lines (such as the CRT screen from 2022 day 10) are stored on
+//! one line, with newlines escaped as `\n` and backslashes as `\\`.
+//!
+//! The `verify` command reruns every day with a stored answer and fails if any of them
+//! no longer match, while `record` appends the answers of newly solved parts.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::Path
+};
+
+use crate::{
+    cli::{Input, Options},
+    runner::{self, paint, Answer, Part, Year}
+};
+
+/// The verified answers for one year, keyed by day and part.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    map: BTreeMap<(u8, Part), String>,
+}
+
+impl Answers {
+    /// Parse the contents of an answers file.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = Self::default();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim_end();
+
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let malformed = || format!("Malformed answer on line {}: '{line}' (expected DD.P: ANSWER.)", i + 1);
+
+            let (key, answer) = line.split_once(": ").ok_or_else(malformed)?;
+            let (day, part) = key.split_once('.').ok_or_else(malformed)?;
+
+            let day = day
+                .parse::<u8>()
+                .ok()
+                .filter(|day| (1..=25).contains(day))
+                .ok_or_else(malformed)?;
+
+            let part = match part {
+                "1" => Part::One,
+                "2" => Part::Two,
+                _ => return Err(malformed())
+            };
+
+            if answers.map.insert((day, part), unescape(answer)).is_some() {
+                return Err(format!("Duplicate answer for day {day} part {part} on line {}.", i + 1));
+            }
+        }
+
+        Ok(answers)
+    }
+
+    /// Load an answers file, treating a missing file as empty.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|err| format!("{}: {err}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(format!("Could not read {}: {err}", path.display()))
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+
+        std::fs::write(path, self.to_string())
+            .map_err(|err| format!("Could not write {}: {err}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.map.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Store an answer, returning the previous one (if any.)
+    pub fn insert(&mut self, day: u8, part: Part, answer: impl Into<String>) -> Option<String> {
+        self.map.insert((day, part), answer.into())
+    }
+
+    /// Whether any answer is stored for the given day.
+    pub fn has_day(&self, day: u8) -> bool {
+        Part::ALL.iter().any(|part| self.map.contains_key(&(day, *part)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u8, Part, &str)> {
+        self.map
+            .iter()
+            .map(|((day, part), answer)| (*day, *part, answer.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# Verified answers for the real inputs, as DAY.PART: ANSWER.")?;
+
+        for (day, part, answer) in self.iter() {
+            writeln!(f, "{day:02}.{part}: {}", escape(answer))?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn escape(answer: &str) -> String {
+    answer
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+}
+
+pub(crate) fn unescape(answer: &str) -> String {
+    let mut unescaped = String::with_capacity(answer.len());
+    let mut chars = answer.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            },
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            },
+            (c, _) => unescaped.push(c)
+        }
+    }
+
+    unescaped
+}
+
+/// Rerun every selected day that has a stored answer, and compare the results.
+///
+/// Prints one line per part, and fails if any stored answer wasn't reproduced.
+pub fn verify(year: &Year, options: &Options) -> Result<(), String> {
+    if options.example || options.input != Input::Default {
+        return Err("Answers are only stored for the real inputs; verify can't be combined with --example or --input.".to_owned());
+    }
+
+    let path = year.path(year.answers);
+    let answers = Answers::load(&path)?;
+    let days: Vec<_> = runner::select_days(year, options)?
+        .into_iter()
+        .filter(|day| answers.has_day(day.day))
+        .collect();
+
+    if days.is_empty() {
+        return Err(format!("No answers are stored in {} for the selected days.", path.display()));
+    }
+
+    runner::install_panic_hook();
+
+    let mut failures = 0;
+
+    for day in days {
+        let outcome = runner::solve(year, day, options)?;
+
+        for part in &outcome.parts {
+            let Some(expected) = answers.get(day.day, part.part) else {
+                println!("Day {:02} part {}: {}", day.day, part.part, paint(&"no stored answer", "33"));
+                continue;
+            };
+
+            match &part.answer {
+                Answer::Solved(actual) if actual == expected => {
+                    println!("Day {:02} part {}: {} ({:?})", day.day, part.part, paint(&"ok", "32"), part.time);
+                },
+                actual => {
+                    failures += 1;
+                    println!(
+                        "Day {:02} part {}: {} (expected {}, got {})",
+                        day.day, part.part, paint(&"MISMATCH", "1;31"), escape(expected), escape(&actual.to_string())
+                    );
+                }
+            }
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        n => Err(format!("{n} answer(s) did not match {}.", path.display()))
+    }
+}
+
+/// Run the selected days and store the answers of any solved parts that aren't
+/// in the answers file yet. Existing answers are never replaced.
+pub fn record(year: &Year, options: &Options) -> Result<(), String> {
+    if options.example || options.input != Input::Default {
+        return Err("Answers are only stored for the real inputs; record can't be combined with --example or --input.".to_owned());
+    }
+
+    let path = year.path(year.answers);
+    let mut answers = Answers::load(&path)?;
+    let days = runner::select_days(year, options)?;
+    let mut added = 0;
+
+    runner::install_panic_hook();
+
+    for day in days {
+        let outcome = runner::solve(year, day, options)?;
+
+        for part in &outcome.parts {
+            let Answer::Solved(answer) = &part.answer else {
+                continue
+            };
+
+            match answers.get(day.day, part.part) {
+                Some(existing) if existing != answer => println!(
+                    "Day {:02} part {}: {} (stored {}, got {})",
+                    day.day, part.part, paint(&"kept stored answer", "33"), escape(existing), escape(answer)
+                ),
+                Some(_) => (),
+                None => {
+                    answers.insert(day.day, part.part, answer.clone());
+                    added += 1;
+                    println!("Day {:02} part {}: recorded {}", day.day, part.part, escape(answer));
+                }
+            }
+        }
+    }
+
+    answers.save(&path)?;
+    println!("Recorded {added} new answer(s) in {}.", path.display());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "\
+# 2022
+01.1: 66306
+01.2: 195292
+
+10.2: \\n## \\\\n\\n
+";
+
+    #[test]
+    fn round_trip() {
+        let answers = Answers::parse(FILE).unwrap();
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers.get(1, Part::Two), Some("195292"));
+        assert_eq!(answers.get(10, Part::Two), Some("\n## \\n\n"));
+        assert_eq!(answers.get(10, Part::One), None);
+        assert!(answers.has_day(10));
+        assert!(!answers.has_day(2));
+
+        assert_eq!(Answers::parse(&answers.to_string()), Ok(answers));
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(Answers::parse("01.3: 5").is_err());
+        assert!(Answers::parse("26.1: 5").is_err());
+        assert!(Answers::parse("01.1 5").is_err());
+        assert!(Answers::parse("01.1: 5\n01.1: 6").is_err());
+    }
+}
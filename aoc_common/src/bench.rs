@@ -0,0 +1,341 @@
+//! Repeated timing of each day's `parse`, `part_one` and `part_two`.
+//!
+//! A single run (as printed by the runner) is at the mercy of whatever else the machine
+//! happens to be doing, so the `bench` command runs each selected day many times and reports
+//! the minimum, median and 95th percentile of every stage instead. Only `std::time` is used.
+//!
+//! With `--save`, the medians are written to a baseline file under `target/` (timings are
+//! specific to the machine, so they don't belong in the repository.) Later runs compare
+//! their medians against it and fail if any stage has slowed down by more than [`TOLERANCE`]
+//! (and more than [`NOISE_FLOOR`].)
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::Path,
+    time::{Duration, Instant}
+};
+
+use crate::{
+    cli::{Input, Options},
+    runner::{self, paint, Answer, Part, Year}
+};
+
+/// Where baselines are saved, relative to the year crate.
+pub const BASELINE: &str = "target/bench_baseline.txt";
+
+/// How much slower (as a fraction of the baseline median) a stage can get before it's flagged.
+pub const TOLERANCE: f64 = 0.10;
+
+/// Stages that slowed down by less than this in absolute terms are never flagged, as
+/// sub-microsecond timings are mostly noise.
+pub const NOISE_FLOOR: Duration = Duration::from_micros(2);
+
+/// Without an explicit `--iterations`, keep sampling a day until this much time has passed...
+const BUDGET: Duration = Duration::from_secs(1);
+/// ...and at least this many samples have been taken, but no more than `MAX_SAMPLES`.
+const MIN_SAMPLES: usize = 5;
+const MAX_SAMPLES: usize = 1000;
+
+/// One timed step of a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    Parse,
+    Part(Part)
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part {part}")
+        }
+    }
+}
+
+impl std::str::FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            "part 1" => Ok(Stage::Part(Part::One)),
+            "part 2" => Ok(Stage::Part(Part::Two)),
+            _ => Err(format!("'{s}' is not a valid stage."))
+        }
+    }
+}
+
+/// Summary statistics over a set of timing samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Summarize a (non-empty) set of samples.
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "Cannot summarize zero samples.");
+        samples.sort_unstable();
+
+        Self {
+            samples: samples.len(),
+            min: samples[0],
+            median: percentile(&samples, 50),
+            p95: percentile(&samples, 95),
+        }
+    }
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[Duration], pct: usize) -> Duration {
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Saved median timings, keyed by day and stage.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+    map: BTreeMap<(u8, Stage), Duration>,
+}
+
+impl Baseline {
+    /// Parse a baseline file, with lines of the form `DD STAGE: NANOS`.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut baseline = Self::default();
+
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let malformed = || format!("Malformed baseline on line {}: '{line}'", i + 1);
+
+            let (key, nanos) = line.split_once(": ").ok_or_else(malformed)?;
+            let (day, stage) = key.split_once(' ').ok_or_else(malformed)?;
+
+            let day = day.parse::<u8>().map_err(|_| malformed())?;
+            let stage = stage.parse::<Stage>().map_err(|_| malformed())?;
+            let nanos = nanos.trim().parse::<u64>().map_err(|_| malformed())?;
+
+            baseline.map.insert((day, stage), Duration::from_nanos(nanos));
+        }
+
+        Ok(baseline)
+    }
+
+    /// Load a baseline file, treating a missing file as empty.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|err| format!("{}: {err}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(format!("Could not read {}: {err}", path.display()))
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|err| format!("Could not create {}: {err}", dir.display()))?;
+        }
+
+        std::fs::write(path, self.to_string())
+            .map_err(|err| format!("Could not write {}: {err}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, stage: Stage) -> Option<Duration> {
+        self.map.get(&(day, stage)).copied()
+    }
+
+    pub fn insert(&mut self, day: u8, stage: Stage, median: Duration) {
+        self.map.insert((day, stage), median);
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# Median timings in nanoseconds, as DAY STAGE: NANOS.")?;
+
+        for ((day, stage), median) in &self.map {
+            writeln!(f, "{day:02} {stage}: {}", median.as_nanos())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// How a stage's median compares to its baseline, as a signed fraction (0.1 is 10% slower.)
+pub fn change(median: Duration, baseline: Duration) -> f64 {
+    let baseline = baseline.as_secs_f64().max(f64::MIN_POSITIVE);
+    (median.as_secs_f64() - baseline) / baseline
+}
+
+/// Benchmark the selected days, compare them to the saved baseline and (optionally) save a new one.
+pub fn bench(year: &Year, options: &Options) -> Result<(), String> {
+    if options.example || options.input != Input::Default {
+        return Err("Benchmarks always use the real inputs; bench can't be combined with --example or --input.".to_owned());
+    }
+
+    let path = year.path(BASELINE);
+    let mut baseline = Baseline::load(&path)?;
+    let days = runner::select_days(year, options)?;
+    let mut regressions = 0;
+
+    runner::install_panic_hook();
+
+    let target = match cfg!(debug_assertions) {
+        true => paint(&"(DEBUG)", "1;33"),
+        false => paint(&"(RELEASE)", "1;32")
+    };
+
+    println!("--- BENCH {} {target} ---", year.year);
+
+    if cfg!(debug_assertions) {
+        println!("{}", paint(&"Debug build; timings will not be representative.", "33"));
+    }
+
+    for day in days {
+        let inputs = runner::load_inputs(year, day, options)?;
+
+        // The first run doubles as a warm-up, and weeds out parts that don't produce an answer.
+        let warmup = runner::solve_inputs(day, &inputs);
+        let inputs: Vec<_> = inputs
+            .into_iter()
+            .filter(|(part, _)| warmup.parts
+                .iter()
+                .any(|p| p.part == *part && matches!(p.answer, Answer::Solved(_)))
+            )
+            .collect();
+
+        for p in warmup.parts.iter().filter(|p| !matches!(p.answer, Answer::Solved(_))) {
+            println!("Day {:02} part {}: {}", day.day, p.part, paint(&p.answer, "31"));
+        }
+
+        if inputs.is_empty() {
+            continue;
+        }
+
+        let mut samples: BTreeMap<Stage, Vec<Duration>> = BTreeMap::new();
+        let start = Instant::now();
+
+        loop {
+            let taken = samples.get(&Stage::Parse).map_or(0, Vec::len);
+
+            let done = match options.iterations {
+                Some(n) => taken >= n,
+                None => taken >= MAX_SAMPLES || (taken >= MIN_SAMPLES && start.elapsed() >= BUDGET)
+            };
+
+            if done {
+                break;
+            }
+
+            let outcome = runner::solve_inputs(day, &inputs);
+
+            samples.entry(Stage::Parse).or_default().push(outcome.parse_time);
+
+            for part in outcome.parts {
+                samples.entry(Stage::Part(part.part)).or_default().push(part.time);
+            }
+        }
+
+        for (stage, samples) in samples {
+            let stats = Stats::new(samples);
+
+            let comparison = match baseline.get(day.day, stage) {
+                Some(base) => {
+                    let change = change(stats.median, base);
+                    let text = format!("{:+.1}% vs {base:?}", change * 100.0);
+
+                    match change > TOLERANCE && stats.median > base + NOISE_FLOOR {
+                        true => {
+                            regressions += 1;
+                            format!("{} {}", paint(&text, "31"), paint(&"REGRESSION", "1;31"))
+                        },
+                        false => paint(&text, "32")
+                    }
+                },
+                None => String::new()
+            };
+
+            let line = format!(
+                "Day {:02} {:<6}  min {:>12}  median {:>12}  p95 {:>12}  ({} samples)  {comparison}",
+                day.day,
+                stage.to_string(),
+                format!("{:?}", stats.min),
+                format!("{:?}", stats.median),
+                format!("{:?}", stats.p95),
+                stats.samples
+            );
+
+            println!("{}", line.trim_end());
+
+            if options.save {
+                baseline.insert(day.day, stage, stats.median);
+            }
+        }
+    }
+
+    if options.save {
+        baseline.save(&path)?;
+        println!("Saved baseline to {}.", path.display());
+    }
+
+    match regressions {
+        0 => Ok(()),
+        n => Err(format!("{n} stage(s) regressed by more than {:.0}% against the baseline.", TOLERANCE * 100.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_micros(*v)).collect()
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::new(micros(&[9, 1, 5, 3, 7, 2, 8, 4, 6, 10]));
+
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(5));
+        assert_eq!(stats.p95, Duration::from_micros(10));
+
+        let single = Stats::new(micros(&[42]));
+        assert_eq!(single.median, Duration::from_micros(42));
+        assert_eq!(single.p95, Duration::from_micros(42));
+    }
+
+    #[test]
+    fn baseline() {
+        let mut baseline = Baseline::default();
+        baseline.insert(1, Stage::Parse, Duration::from_nanos(1500));
+        baseline.insert(12, Stage::Part(Part::Two), Duration::from_micros(342));
+
+        let text = baseline.to_string();
+        assert!(text.contains("12 part 2: 342000"));
+        assert_eq!(Baseline::parse(&text), Ok(baseline));
+
+        assert!(Baseline::parse("01 part 3: 5").is_err());
+        assert!(Baseline::parse("01 parse 5").is_err());
+    }
+
+    #[test]
+    fn regression() {
+        let base = Duration::from_micros(100);
+
+        assert!(change(Duration::from_micros(120), base) > TOLERANCE);
+        assert!(change(Duration::from_micros(105), base) < TOLERANCE);
+        assert!(change(Duration::from_micros(50), base) < 0.0);
+    }
+}
@@ -0,0 +1,192 @@
+//! Build-time discovery of a year crate's day modules.
+//!
+//! Rather than keeping a list of `mod day_NN;` declarations and a day count in `main.rs` up to date
+//! by hand, each year crate has a build script that calls [`generate_days`]:
+//! ``` ignore
+//! fn main() {
+//!     aoc_common::build::generate_days();
+//! }
+//! ```
+//! ... and pulls the result into `main.rs` with:
+//! ``` ignore
+//! include!(concat!(env!("OUT_DIR"), "/days.rs"));
+//! ```
+//! The generated file declares a module for every `src/day_NN.rs` and a `DAYS` dispatch table
+//! (see [`days!`](crate::days)) registering each of them, so adding a day is just a matter of
+//! dropping its file into `src/`.
+//!
+//! It also contains a test for every case and part of the [multi-case example files](crate::examples)
+//! in `src/inputs/`, which expects the crate to define its [`Year`](crate::runner::Year) as `YEAR`.
+
+use std::{
+    collections::HashSet,
+    fmt::Write,
+    path::{Path, PathBuf}
+};
+
+use crate::{examples, runner::Part};
+
+/// Find the days that have a `day_NN.rs` file in `dir`, in order.
+pub fn discover_days(dir: &Path) -> std::io::Result<Vec<u8>> {
+    let mut days: Vec<u8> = std::fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .filter_map(|entry| day_of(&entry.file_name().to_string_lossy()))
+        .collect();
+
+    days.sort_unstable();
+    Ok(days)
+}
+
+/// The day number of a file named `day_NN.rs` (with `NN` in `01..=25`), if it is one.
+fn day_of(file_name: &str) -> Option<u8> {
+    let digits = file_name
+        .strip_prefix("day_")?
+        .strip_suffix(".rs")?;
+
+    if digits.len() != 2 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    digits
+        .parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+}
+
+/// Render the module declarations and dispatch table for the given days.
+///
+/// Module paths are made absolute, as `include!`d files don't resolve `mod` relative to `src/`.
+pub fn render(src_dir: &Path, days: &[u8]) -> String {
+    let mut out = String::from("// Generated by aoc_common::build from the day_NN.rs files in src/ - do not edit.\n\n");
+
+    for day in days {
+        let path = src_dir.join(format!("day_{day:02}.rs"));
+        let _ = writeln!(out, "#[path = {:?}]\nmod day_{day:02};", path.display().to_string());
+    }
+
+    let list = days
+        .iter()
+        .map(u8::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let _ = writeln!(
+        out,
+        "\nstatic DAYS: [::aoc_common::runner::Day; {}] = ::aoc_common::days!(Solutions; {list});",
+        days.len()
+    );
+
+    out
+}
+
+/// Render a `#[test]` for every case and part with an expected answer in the days' example files.
+pub fn render_examples(src_dir: &Path, days: &[u8]) -> Result<String, String> {
+    let mut tests = String::new();
+
+    for day in days {
+        let path = src_dir.join(format!("inputs/test_{day:02}.txt"));
+
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue
+        };
+
+        if !examples::has_cases(&text) {
+            continue;
+        }
+
+        let cases = examples::parse(&text).map_err(|err| format!("{}: {err}", path.display()))?;
+        let mut idents = HashSet::new();
+
+        for (i, case) in cases.iter().enumerate() {
+            let mut ident = examples::ident(&case.name);
+
+            if !idents.insert(ident.clone()) {
+                ident = format!("{ident}_{}", i + 1);
+                idents.insert(ident.clone());
+            }
+
+            for part in Part::ALL.into_iter().filter(|part| case.expected(*part).is_some()) {
+                let suffix = match part {
+                    Part::One => "part_one",
+                    Part::Two => "part_two"
+                };
+
+                let _ = writeln!(
+                    tests,
+                    "\n    #[test]\n    fn day_{day:02}_{ident}_{suffix}() {{\n        \
+                     ::aoc_common::examples::check(&super::YEAR, {day}, {:?}, ::aoc_common::runner::Part::{part:?});\n    }}",
+                    case.name
+                );
+            }
+        }
+    }
+
+    match tests.is_empty() {
+        true => Ok(String::new()),
+        false => Ok(format!("\n#[cfg(test)]\nmod examples {{{tests}}}\n"))
+    }
+}
+
+/// Discover the crate's day modules and write `days.rs` into `OUT_DIR`.
+///
+/// Must be called from a build script.
+pub fn generate_days() {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set; is this a build script?");
+    let out_dir = std::env::var_os("OUT_DIR").expect("OUT_DIR is not set; is this a build script?");
+
+    let src_dir = PathBuf::from(manifest_dir).join("src");
+    let days = discover_days(&src_dir).expect("Could not read the src directory.");
+
+    let mut generated = render(&src_dir, &days);
+    generated += &render_examples(&src_dir, &days).unwrap_or_else(|err| panic!("{err}"));
+
+    std::fs::write(PathBuf::from(out_dir).join("days.rs"), generated)
+        .expect("Could not write days.rs.");
+
+    // Rerun whenever anything in src/ (including the example files) changes.
+    println!("cargo:rerun-if-changed=src");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_names() {
+        assert_eq!(day_of("day_01.rs"), Some(1));
+        assert_eq!(day_of("day_25.rs"), Some(25));
+        assert_eq!(day_of("day_n.rs"), None);
+        assert_eq!(day_of("day_1.rs"), None);
+        assert_eq!(day_of("day_26.rs"), None);
+        assert_eq!(day_of("day_01.rs.orig"), None);
+        assert_eq!(day_of("main.rs"), None);
+    }
+
+    #[test]
+    fn rendering() {
+        let out = render(Path::new("/aoc/src"), &[1, 2, 5]);
+
+        assert!(out.contains("#[path = \"/aoc/src/day_05.rs\"]\nmod day_05;"));
+        assert!(out.contains("static DAYS: [::aoc_common::runner::Day; 3] = ::aoc_common::days!(Solutions; 1, 2, 5);"));
+    }
+
+    #[test]
+    fn example_tests() {
+        let src = std::env::temp_dir().join(format!("aoc_common_build_{}", std::process::id()));
+        std::fs::create_dir_all(src.join("inputs")).unwrap();
+        std::fs::write(src.join("inputs/test_01.txt"), "1\n2\n").unwrap();
+        std::fs::write(src.join("inputs/test_02.txt"), "--- CASE Small one ---\n--- PART TWO: 3 ---\nabc\n").unwrap();
+
+        let out = render_examples(&src, &[1, 2, 3]).unwrap();
+
+        assert!(out.contains("fn day_02_small_one_part_two()"));
+        assert!(out.contains("check(&super::YEAR, 2, \"Small one\", ::aoc_common::runner::Part::Two)"));
+        assert!(!out.contains("part_one"));
+        assert!(!out.contains("day_01"));
+
+        std::fs::write(src.join("inputs/test_02.txt"), "--- CASE a ---\n--- PART 2: 3 ---\n").unwrap();
+        assert!(render_examples(&src, &[2]).is_err());
+
+        std::fs::remove_dir_all(src).unwrap();
+    }
+}
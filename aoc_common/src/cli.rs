@@ -0,0 +1,298 @@
+//! Command line option parsing for the [`runner`](crate::runner).
+//!
+//! Deliberately hand-rolled to keep the year crates `std` only.
+
+use std::{ops::RangeInclusive, path::PathBuf};
+
+use crate::{extract, params, report::Format, runner::Part};
+
+pub const USAGE: &str = "\
+USAGE:
+    advent_of_code [COMMAND] [DAYS] [OPTIONS]
+
+COMMANDS:
+    run                      Run the selected days (the default)
+    verify                   Rerun the selected days against the answers file, failing on any mismatch
+    record                   Add the answers of newly solved parts to the answers file
+    bench                    Time the selected days repeatedly and compare against the saved baseline
+    new                      Create src/day_NN.rs from the year's src/day_n.rs template, plus empty input files
+    submit                   Submit the answer to the selected part (or the first unsolved one) of a single day
+    fetch                    Download the inputs of the selected days (or all of them) that are missing
+    encrypt                  Encrypt new or changed inputs of the selected days (or all of them) for committing
+    watch                    Rebuild and rerun a single day (tests, then the real input) whenever its files change
+    stats                    Chart the solve times and ranks noted in the day modules of each year (or the selected one)
+    extract                  List the code blocks of a saved puzzle page, or write the chosen ones to test_NN.txt
+
+DAYS is the same as --day (e.g. new 7, verify 3..7.)
+
+OPTIONS:
+    -y, --year <YEAR>        Select the year (defaults to the most recent one)
+    -d, --day <DAYS>         Only run the given day (e.g. 5) or inclusive range of days (e.g. 3..7)
+        --part <PART>        Only run part 1 or part 2
+    -e, --example            Run against the example input instead of the real input
+    -i, --input <PATH>       Read the input from PATH instead, or from standard input if PATH is -
+    -f, --format <FORMAT>    Print results as pretty (default), json (one object per line) or csv
+    -p, --param <OVERRIDE>   Override a puzzle parameter, as DAY.NAME=VALUE
+    -n, --iterations <N>     (bench) Take exactly N samples per day, instead of sampling for about a second
+        --save               (bench) Save the medians as the new baseline
+        --readme             (stats) Also regenerate the completion table in README.md
+        --page <PATH>        (extract) The saved puzzle page to read
+        --block <N[,M...]>   (extract) Write these code blocks (numbered from 1) as the day's examples
+    -h, --help               Print this message";
+
+/// What the runner should do with the selected days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Command {
+    #[default]
+    Run,
+    Verify,
+    Record,
+    Bench,
+    New,
+    Submit,
+    Fetch,
+    Encrypt,
+    Extract,
+    Stats,
+    Watch
+}
+
+impl std::str::FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "run" => Ok(Command::Run),
+            "verify" => Ok(Command::Verify),
+            "record" => Ok(Command::Record),
+            "bench" => Ok(Command::Bench),
+            "new" => Ok(Command::New),
+            "submit" => Ok(Command::Submit),
+            "fetch" => Ok(Command::Fetch),
+            "encrypt" => Ok(Command::Encrypt),
+            "extract" => Ok(Command::Extract),
+            "stats" => Ok(Command::Stats),
+            "watch" => Ok(Command::Watch),
+            _ => Err(format!("Unrecognized command '{s}'."))
+        }
+    }
+}
+
+/// Where to read the puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Input {
+    /// Use the year crate's `Solver::load`/`Solver::load_test`.
+    #[default]
+    Default,
+    Path(PathBuf),
+    Stdin
+}
+
+/// Options parsed from the command line.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    pub command: Command,
+    pub year: Option<u16>,
+    pub days: Option<RangeInclusive<u8>>,
+    pub part: Option<Part>,
+    pub example: bool,
+    pub input: Input,
+    pub format: Format,
+    pub iterations: Option<usize>,
+    pub save: bool,
+    pub page: Option<PathBuf>,
+    pub blocks: Vec<usize>,
+    pub readme: bool,
+    pub help: bool,
+}
+
+impl Options {
+    /// Parse options out of the process arguments (minus the program name.)
+    ///
+    /// Parameter overrides are applied immediately as a side effect.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter().peekable();
+
+        if let Some(command) = args.next_if(|arg| !arg.starts_with('-')) {
+            options.command = command.parse()?;
+
+            if let Some(days) = args.next_if(|arg| !arg.starts_with('-')) {
+                options.days = Some(parse_days(&days)?);
+            }
+        }
+
+        while let Some(arg) = args.next() {
+            // Support both "--day 5" and "--day=5".
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag.to_owned(), Some(value.to_owned())),
+                _ => (arg.clone(), None)
+            };
+
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("Missing value for {flag}."))
+            };
+
+            match flag.as_str() {
+                "-y" | "--year" => options.year = Some(parse_year(&value()?)?),
+                "-d" | "--day" | "--days" => options.days = Some(parse_days(&value()?)?),
+                "--part" => options.part = Some(parse_part(&value()?)?),
+                "-e" | "--example" => options.example = true,
+                "-i" | "--input" => {
+                    options.input = match value()?.as_str() {
+                        "-" => Input::Stdin,
+                        path => Input::Path(PathBuf::from(path))
+                    }
+                },
+                "-f" | "--format" => options.format = value()?.parse()?,
+                "-n" | "--iterations" => {
+                    options.iterations = match value()?.parse::<usize>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => return Err(format!("Invalid iteration count for {flag} (expected a positive integer.)"))
+                    }
+                },
+                "--save" => options.save = true,
+                "--readme" => options.readme = true,
+                "--page" => options.page = Some(PathBuf::from(value()?)),
+                "--block" | "--blocks" => options.blocks = extract::parse_blocks(&value()?)?,
+                "-p" | "--param" => params::apply_override(&value()?)?,
+                "-h" | "--help" => options.help = true,
+                _ => return Err(format!("Unrecognized argument '{arg}'."))
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// The parts selected to run, in order.
+    pub fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec()
+        }
+    }
+}
+
+/// Parse a day (`5`) or an inclusive range of days (`3..7`, `3..=7` or `3-7`.)
+pub fn parse_days(value: &str) -> Result<RangeInclusive<u8>, String> {
+    let parse_day = |day: &str| {
+        day.trim()
+            .parse::<u8>()
+            .ok()
+            .filter(|day| (1..=25).contains(day))
+            .ok_or_else(|| format!("'{day}' is not a valid day (expected 1 through 25.)"))
+    };
+
+    let range = match value
+        .split_once("..=")
+        .or_else(|| value.split_once(".."))
+        .or_else(|| value.split_once('-'))
+    {
+        Some((start, end)) => parse_day(start)?..=parse_day(end)?,
+        None => {
+            let day = parse_day(value)?;
+            day..=day
+        }
+    };
+
+    match range.is_empty() {
+        true => Err(format!("Day range '{value}' is empty.")),
+        false => Ok(range)
+    }
+}
+
+fn parse_year(value: &str) -> Result<u16, String> {
+    value
+        .parse::<u16>()
+        .ok()
+        .filter(|year| *year >= 2015)
+        .ok_or_else(|| format!("'{value}' is not a valid year (expected e.g. 2022.)"))
+}
+
+fn parse_part(value: &str) -> Result<Part, String> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("'{value}' is not a valid part (expected 1 or 2.)"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn days() {
+        assert_eq!(parse_days("5"), Ok(5..=5));
+        assert_eq!(parse_days("3..7"), Ok(3..=7));
+        assert_eq!(parse_days("3..=7"), Ok(3..=7));
+        assert_eq!(parse_days("3-7"), Ok(3..=7));
+        assert!(parse_days("7..3").is_err());
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("26").is_err());
+    }
+
+    #[test]
+    fn options() {
+        let options = parse(&["--day", "15", "--part=2", "-e", "-i", "-"]).unwrap();
+
+        assert_eq!(options.command, Command::Run);
+        assert_eq!(options.year, None);
+        assert_eq!(options.days, Some(15..=15));
+        assert_eq!(options.parts(), [Part::Two]);
+        assert!(options.example);
+        assert_eq!(options.input, Input::Stdin);
+
+        let options = parse(&["-d", "1..3", "--input", "puzzle.txt", "-f", "csv"]).unwrap();
+
+        assert_eq!(options.parts(), Part::ALL);
+        assert_eq!(options.format, Format::Csv);
+        assert_eq!(options.input, Input::Path(PathBuf::from("puzzle.txt")));
+
+        let options = parse(&["verify", "-d", "3", "--year", "2022"]).unwrap();
+
+        assert_eq!(options.command, Command::Verify);
+        assert_eq!(options.year, Some(2022));
+        assert_eq!(options.days, Some(3..=3));
+
+        assert!(parse(&["-y", "22"]).is_err());
+
+        assert!(parse(&["--part", "3"]).is_err());
+        let options = parse(&["bench", "-n", "50", "--save"]).unwrap();
+
+        assert_eq!(options.command, Command::Bench);
+        assert_eq!(options.iterations, Some(50));
+        assert!(options.save);
+
+        assert!(parse(&["bench", "-n", "0"]).is_err());
+        assert_eq!(parse(&["new", "7"]).unwrap().days, Some(7..=7));
+        assert_eq!(parse(&["submit", "7", "--part", "2"]).unwrap().command, Command::Submit);
+        assert!(parse(&["new", "7", "8"]).is_err());
+
+        let options = parse(&["extract", "18", "--page", "day18.html", "--block", "1,3"]).unwrap();
+
+        assert_eq!(options.command, Command::Extract);
+        assert_eq!(options.page, Some(PathBuf::from("day18.html")));
+        assert_eq!(options.blocks, [1, 3]);
+        assert!(parse(&["extract", "18", "--block", "0"]).is_err());
+
+        let options = parse(&["stats", "--readme"]).unwrap();
+
+        assert_eq!(options.command, Command::Stats);
+        assert!(options.readme);
+        assert_eq!(parse(&["watch", "16", "--part", "1"]).unwrap().command, Command::Watch);
+
+        assert!(parse(&["frobnicate"]).is_err());
+        assert!(parse(&["-d", "3", "verify"]).is_err());
+        assert!(parse(&["--format", "xml"]).is_err());
+        assert!(parse(&["--day"]).is_err());
+        assert!(parse(&["--frobnicate"]).is_err());
+    }
+}
@@ -0,0 +1,272 @@
+//! ChaCha20-Poly1305 authenticated encryption (RFC 8439), for keeping puzzle inputs encrypted at rest.
+//!
+//! Written out by hand to keep the year crates `std` only. It follows the RFC closely and is
+//! checked against reference outputs below, but it makes no attempt at being constant time;
+//! it's meant for keeping inputs unreadable in a public repository (as long as they were never
+//! committed in plain), not for anything more sensitive.
+
+pub const KEY_LEN: usize = 32;
+pub const NONCE_LEN: usize = 12;
+pub const TAG_LEN: usize = 16;
+
+pub type Key = [u8; KEY_LEN];
+pub type Nonce = [u8; NONCE_LEN];
+
+/// Encrypt `plaintext`, returning the ciphertext followed by its authentication tag.
+pub fn seal(key: &Key, nonce: &Nonce, plaintext: &[u8]) -> Vec<u8> {
+    let mut sealed = plaintext.to_vec();
+    chacha20(key, nonce, 1, &mut sealed);
+
+    let tag = tag(key, nonce, &sealed);
+    sealed.extend_from_slice(&tag);
+    sealed
+}
+
+/// Decrypt the output of [`seal`], or fail if it was tampered with (or the key is wrong.)
+pub fn open(key: &Key, nonce: &Nonce, sealed: &[u8]) -> Option<Vec<u8>> {
+    let split = sealed.len().checked_sub(TAG_LEN)?;
+    let (ciphertext, expected) = sealed.split_at(split);
+
+    let actual = tag(key, nonce, ciphertext);
+    let difference = actual
+        .iter()
+        .zip(expected)
+        .fold(0, |acc, (a, b)| acc | (a ^ b));
+
+    if difference != 0 {
+        return None;
+    }
+
+    let mut plaintext = ciphertext.to_vec();
+    chacha20(key, nonce, 1, &mut plaintext);
+    Some(plaintext)
+}
+
+/// The Poly1305 tag over `ciphertext` (with no additional data), keyed from the first ChaCha20 block.
+fn tag(key: &Key, nonce: &Nonce, ciphertext: &[u8]) -> [u8; TAG_LEN] {
+    let block = block(key, nonce, 0);
+    let mut one_time_key = [0; 32];
+    one_time_key.copy_from_slice(&block[..32]);
+
+    let mut data = ciphertext.to_vec();
+    data.resize(ciphertext.len().next_multiple_of(16), 0);
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&(ciphertext.len() as u64).to_le_bytes());
+
+    poly1305(&one_time_key, &data)
+}
+
+fn quarter_round(s: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    s[a] = s[a].wrapping_add(s[b]); s[d] = (s[d] ^ s[a]).rotate_left(16);
+    s[c] = s[c].wrapping_add(s[d]); s[b] = (s[b] ^ s[c]).rotate_left(12);
+    s[a] = s[a].wrapping_add(s[b]); s[d] = (s[d] ^ s[a]).rotate_left(8);
+    s[c] = s[c].wrapping_add(s[d]); s[b] = (s[b] ^ s[c]).rotate_left(7);
+}
+
+/// One 64-byte block of ChaCha20 keystream.
+fn block(key: &Key, nonce: &Nonce, counter: u32) -> [u8; 64] {
+    let word = |bytes: &[u8]| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+    let mut state = [0u32; 16];
+    state[..4].copy_from_slice(&[0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574]);
+
+    for i in 0..8 {
+        state[4 + i] = word(&key[i * 4..]);
+    }
+
+    state[12] = counter;
+
+    for i in 0..3 {
+        state[13 + i] = word(&nonce[i * 4..]);
+    }
+
+    let mut working = state;
+
+    for _ in 0..10 {
+        quarter_round(&mut working, 0, 4, 8, 12);
+        quarter_round(&mut working, 1, 5, 9, 13);
+        quarter_round(&mut working, 2, 6, 10, 14);
+        quarter_round(&mut working, 3, 7, 11, 15);
+        quarter_round(&mut working, 0, 5, 10, 15);
+        quarter_round(&mut working, 1, 6, 11, 12);
+        quarter_round(&mut working, 2, 7, 8, 13);
+        quarter_round(&mut working, 3, 4, 9, 14);
+    }
+
+    let mut out = [0; 64];
+
+    for (i, (w, s)) in working.iter().zip(state).enumerate() {
+        out[i * 4..i * 4 + 4].copy_from_slice(&w.wrapping_add(s).to_le_bytes());
+    }
+
+    out
+}
+
+/// XOR `data` with the ChaCha20 keystream, starting from block `counter`.
+fn chacha20(key: &Key, nonce: &Nonce, counter: u32, data: &mut [u8]) {
+    for (i, chunk) in data.chunks_mut(64).enumerate() {
+        let keystream = block(key, nonce, counter.wrapping_add(i as u32));
+
+        for (byte, k) in chunk.iter_mut().zip(keystream) {
+            *byte ^= k;
+        }
+    }
+}
+
+/// Poly1305 over `message`, using 26-bit limbs so that every product fits in a `u64`.
+fn poly1305(key: &[u8; 32], message: &[u8]) -> [u8; TAG_LEN] {
+    const MASK: u32 = 0x3ff_ffff;
+    let word = |bytes: &[u8]| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+    // r, clamped as the RFC requires.
+    let r = [
+        word(&key[0..]) & 0x3ff_ffff,
+        (word(&key[3..]) >> 2) & 0x3ff_ff03,
+        (word(&key[6..]) >> 4) & 0x3ff_c0ff,
+        (word(&key[9..]) >> 6) & 0x3f0_3fff,
+        (word(&key[12..]) >> 8) & 0x00f_ffff,
+    ].map(u64::from);
+
+    let s = [r[1] * 5, r[2] * 5, r[3] * 5, r[4] * 5];
+    let mut h = [0u64; 5];
+
+    for chunk in message.chunks(16) {
+        let mut padded = [0u8; 17];
+        padded[..chunk.len()].copy_from_slice(chunk);
+        padded[chunk.len()] = 1;
+
+        h[0] += u64::from(word(&padded[0..]) & MASK);
+        h[1] += u64::from((word(&padded[3..]) >> 2) & MASK);
+        h[2] += u64::from((word(&padded[6..]) >> 4) & MASK);
+        h[3] += u64::from((word(&padded[9..]) >> 6) & MASK);
+        h[4] += u64::from((word(&padded[12..]) >> 8) | (u32::from(padded[16]) << 24));
+
+        let d = [
+            h[0] * r[0] + h[1] * s[3] + h[2] * s[2] + h[3] * s[1] + h[4] * s[0],
+            h[0] * r[1] + h[1] * r[0] + h[2] * s[3] + h[3] * s[2] + h[4] * s[1],
+            h[0] * r[2] + h[1] * r[1] + h[2] * r[0] + h[3] * s[3] + h[4] * s[2],
+            h[0] * r[3] + h[1] * r[2] + h[2] * r[1] + h[3] * r[0] + h[4] * s[3],
+            h[0] * r[4] + h[1] * r[3] + h[2] * r[2] + h[3] * r[1] + h[4] * r[0],
+        ];
+
+        let mut carry = 0;
+
+        for i in 0..5 {
+            let limb = d[i] + carry;
+            h[i] = limb & u64::from(MASK);
+            carry = limb >> 26;
+        }
+
+        h[0] += carry * 5;
+        h[1] += h[0] >> 26;
+        h[0] &= u64::from(MASK);
+    }
+
+    // Fully carry h, then compute g = h + 5 - 2^130 and use it instead if it didn't underflow
+    // (i.e. reduce h modulo p = 2^130 - 5.)
+    let mut h = h.map(|limb| limb as u32);
+    let mut carry = 0;
+
+    for limb in h.iter_mut().skip(1) {
+        *limb += carry;
+        carry = *limb >> 26;
+        *limb &= MASK;
+    }
+
+    h[0] += carry * 5;
+    h[1] += h[0] >> 26;
+    h[0] &= MASK;
+
+    let mut g = [0u32; 5];
+    let mut carry = 5;
+
+    for i in 0..4 {
+        g[i] = h[i] + carry;
+        carry = g[i] >> 26;
+        g[i] &= MASK;
+    }
+
+    g[4] = (h[4] + carry).wrapping_sub(1 << 26);
+
+    let h = match g[4] >> 31 {
+        0 => g,
+        _ => h
+    };
+
+    // Pack into 128 bits and add s.
+    let packed = [
+        h[0] | (h[1] << 26),
+        (h[1] >> 6) | (h[2] << 20),
+        (h[2] >> 12) | (h[3] << 14),
+        (h[3] >> 18) | (h[4] << 8),
+    ];
+
+    let mut out = [0; TAG_LEN];
+    let mut carry = 0u64;
+
+    for i in 0..4 {
+        let sum = u64::from(packed[i]) + u64::from(word(&key[16 + i * 4..])) + carry;
+        out[i * 4..i * 4 + 4].copy_from_slice(&(sum as u32).to_le_bytes());
+        carry = sum >> 32;
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn poly1305_rfc_vector() {
+        let key: [u8; 32] = [
+            0x85, 0xd6, 0xbe, 0x78, 0x57, 0x55, 0x6d, 0x33, 0x7f, 0x44, 0x52, 0xfe, 0x42, 0xd5, 0x06, 0xa8,
+            0x01, 0x03, 0x80, 0x8a, 0xfb, 0x0d, 0xb2, 0xfd, 0x4a, 0xbf, 0xf6, 0xaf, 0x41, 0x49, 0xf5, 0x1b,
+        ];
+
+        assert_eq!(
+            hex(&poly1305(&key, b"Cryptographic Forum Research Group")),
+            "a8061dc1305136c6c22b8baf0c0127a9"
+        );
+    }
+
+    #[test]
+    fn seal_and_open() {
+        let key: Key = std::array::from_fn(|i| i as u8);
+        let nonce: Nonce = std::array::from_fn(|i| i as u8);
+        let long: Vec<u8> = (0..100).collect();
+
+        // Reference outputs from an independent ChaCha20-Poly1305 implementation.
+        let cases: [(&[u8], &str); 3] = [
+            (b"", "295a498b8841a1c5f55d4d606f731159"),
+            (b"1721\n979\n366\n", "b8cc3a31232e9279bdb009c59238dcbcea57b15a0790067f13c4c2a63e"),
+            (&long, "89fa0a032d12a347bf8a35f89410006cd961a0f44561bbaefe8e35de69ddb823cca10ed0c23b97bf1f1b5cf3\
+                     49b9a10c4eb59b47c91d8eac2a81e33cac72a0e93939fe8ea1516aae8c5f07f7543192be8a8f15613b3fa669\
+                     560eaa5584205ce02dbf0e90ea8c31f2c549d9f8c26c82fd3d277e13"),
+        ];
+
+        for (plaintext, expected) in cases {
+            let sealed = seal(&key, &nonce, plaintext);
+            assert_eq!(hex(&sealed), expected);
+            assert_eq!(open(&key, &nonce, &sealed).as_deref(), Some(plaintext));
+        }
+    }
+
+    #[test]
+    fn tampering() {
+        let key: Key = [7; KEY_LEN];
+        let nonce: Nonce = [9; NONCE_LEN];
+        let mut sealed = seal(&key, &nonce, b"498\n");
+
+        assert_eq!(open(&[8; KEY_LEN], &nonce, &sealed), None);
+        assert_eq!(open(&key, &[0; NONCE_LEN], &sealed), None);
+
+        sealed[0] ^= 1;
+        assert_eq!(open(&key, &nonce, &sealed), None);
+        assert_eq!(open(&key, &nonce, &[0; 4]), None);
+    }
+}
@@ -0,0 +1,106 @@
+//! Cycle detection over a sequence of states, for extrapolating simulations far past what can be run.
+//!
+//! Each state is given as a `(key, value)` pair. The key is a fingerprint of everything that
+//! decides what happens next (e.g. the next rock, the jet index and the shape of the surface),
+//! and the value is whatever is being tracked (e.g. the height of the tower.) Once a key repeats,
+//! every later state repeats too, with the value growing by the same amount each time around.
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Add, Mul, Sub}
+};
+
+/// A cycle found by [`find`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<V> {
+    /// The number of states before the cycle starts.
+    pub prefix: usize,
+    /// The number of states in one time around the cycle.
+    pub period: usize,
+    /// How much the value changes over one time around the cycle.
+    pub delta: V,
+    /// The values of every state seen while looking for the cycle.
+    values: Vec<V>,
+}
+
+impl<V> Cycle<V>
+where
+    V: Copy + Add<Output = V> + Mul<Output = V> + TryFrom<usize>
+{
+    /// The value of the state at `index` (counting from zero), extrapolated through the cycle if needed.
+    pub fn value(&self, index: usize) -> V {
+        if index < self.values.len() {
+            return self.values[index];
+        }
+
+        let offset = index - self.prefix;
+        let laps = V::try_from(offset / self.period)
+            .unwrap_or_else(|_| panic!("{} laps of the cycle don't fit in the value type.", offset / self.period));
+
+        self.values[self.prefix + offset % self.period] + self.delta * laps
+    }
+}
+
+/// Follow `states` until a key repeats, returning the cycle, or `None` if they run out first.
+pub fn find<K, V>(states: impl IntoIterator<Item = (K, V)>) -> Option<Cycle<V>>
+where
+    K: Hash + Eq,
+    V: Copy + Sub<Output = V>
+{
+    let mut seen = HashMap::new();
+    let mut values = Vec::new();
+
+    for (index, (key, value)) in states.into_iter().enumerate() {
+        values.push(value);
+
+        if let Some(prefix) = seen.insert(key, index) {
+            return Some(Cycle {
+                prefix,
+                period: index - prefix,
+                delta: value - values[prefix],
+                values
+            });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extrapolation() {
+        // x -> x² + 1 (mod 97), tracking the running total.
+        let states = || {
+            std::iter::successors(Some(3_u64), |x| Some((x * x + 1) % 97))
+                .scan(0, |total, x| {
+                    *total += x;
+                    Some((x, *total))
+                })
+        };
+
+        let cycle = find(states()).unwrap();
+        let totals: Vec<u64> = states().map(|(_, total)| total).take(1000).collect();
+
+        assert!(cycle.period > 1);
+        assert_eq!(cycle.delta, totals[cycle.prefix + cycle.period] - totals[cycle.prefix]);
+
+        for (index, total) in totals.iter().enumerate() {
+            assert_eq!(cycle.value(index), *total);
+        }
+    }
+
+    #[test]
+    fn no_cycle() {
+        assert_eq!(find((0..100).map(|i| (i, i))), None);
+
+        let cycle = find([(1, 10), (2, 20), (3, 25), (2, 40)]).unwrap();
+
+        assert_eq!((cycle.prefix, cycle.period, cycle.delta), (1, 2, 20));
+        assert_eq!(cycle.value(5), 60);
+        assert_eq!(cycle.value(1_000_001), 20 + 20 * 500_000);
+    }
+}
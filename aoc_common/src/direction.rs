@@ -0,0 +1,120 @@
+use std::fmt::Display;
+
+/// One of the four cardinal directions.
+///
+/// Deltas use screen coordinates - rows (and `y`) grow *downwards*, so
+/// [`Up`](Direction::Up) is a step of `-1` on the vertical axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right
+}
+
+impl Direction {
+    /// All four directions, in clockwise order starting from [`Up`](Direction::Up).
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left
+    ];
+
+    /// Parse a direction from any of the notations the puzzles like to use:
+    /// `U`/`D`/`L`/`R`, `N`/`S`/`W`/`E` or the arrows `^`/`v`/`<`/`>`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | 'N' | '^' => Some(Self::Up),
+            'D' | 'S' | 'v' => Some(Self::Down),
+            'L' | 'W' | '<' => Some(Self::Left),
+            'R' | 'E' | '>' => Some(Self::Right),
+            _ => None
+        }
+    }
+
+    /// The `(row, col)` delta of a single step in this direction.
+    pub fn delta(&self) -> (isize, isize) {
+        match self {
+            Self::Up => (-1, 0),
+            Self::Down => (1, 0),
+            Self::Left => (0, -1),
+            Self::Right => (0, 1)
+        }
+    }
+
+    /// The direction after a 90 degree clockwise turn.
+    pub fn turn_right(&self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up
+        }
+    }
+
+    /// The direction after a 90 degree counter-clockwise turn.
+    pub fn turn_left(&self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Left => Self::Down,
+            Self::Down => Self::Right,
+            Self::Right => Self::Up
+        }
+    }
+
+    /// The opposite direction.
+    pub fn reverse(&self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left
+        }
+    }
+
+    /// Whether or not this direction moves along the vertical axis.
+    pub fn is_vertical(&self) -> bool {
+        matches!(self, Self::Up | Self::Down)
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let arrow = match self {
+            Self::Up => '^',
+            Self::Down => 'v',
+            Self::Left => '<',
+            Self::Right => '>'
+        };
+
+        write!(f, "{arrow}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+        }
+    }
+
+    #[test]
+    fn notations() {
+        for (chars, dir) in ["UN^", "DSv", "LW<", "RE>"].iter().zip([
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right
+        ]) {
+            assert!(chars.chars().all(|c| Direction::from_char(c) == Some(dir)));
+        }
+
+        assert_eq!(Direction::from_char('x'), None);
+    }
+}
@@ -0,0 +1,229 @@
+//! Example files holding several named cases, each with its expected answers inline.
+//!
+//! `lib_aoc`'s `Test::expected` only has room for one answer per part, but plenty of puzzles
+//! come with a handful of small examples. A `test_NN.txt` file can list them all instead:
+//! ``` text
+//! --- CASE first ---
+//! --- PART ONE: 7 ---
+//! --- PART TWO: 19 ---
+//! mjqjpqmgbljsphdztnvjfqwrcgsmlb
+//! --- CASE second ---
+//! --- PART ONE: 5 ---
+//! bvwbjplbgvbhsrlpgdsgbwzqjn
+//! ```
+//! Each case starts with a `--- CASE name ---` line, followed by an answer line for each part
+//! it has an answer for (multi-line answers are escaped as in the [answers file](crate::answers)),
+//! followed by its input. A case without an answer for a part simply isn't tested on it.
+//!
+//! The [`build`](crate::build) step generates one `#[test]` per case and part, each of which
+//! calls [`check`]. Outside of tests, `Solver::load_test` (and so `--example`) uses the first
+//! case with an answer for the part being run.
+
+use crate::{
+    answers,
+    params::{self, Mode},
+    runner::{Answer, Part, Year}
+};
+
+const CASE: &str = "--- CASE";
+
+/// One example input and its expected answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub name: String,
+    pub input: String,
+    /// The expected answers to part one and part two, in that order.
+    pub expected: [Option<String>; 2],
+}
+
+impl Case {
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        self.expected[part.flag() as usize].as_deref()
+    }
+}
+
+/// Whether an example file is in the multi-case format (rather than being a bare input.)
+pub fn has_cases(text: &str) -> bool {
+    text.trim_start().starts_with(CASE)
+}
+
+/// Parse a multi-case example file.
+pub fn parse(text: &str) -> Result<Vec<Case>, String> {
+    let mut cases: Vec<Case> = Vec::new();
+    let mut lines = text.lines().enumerate().peekable();
+
+    while let Some((i, line)) = lines.next() {
+        let line = line.trim_end();
+
+        if line.is_empty() && cases.is_empty() {
+            continue;
+        }
+
+        let name = line
+            .strip_prefix(CASE)
+            .and_then(|rest| rest.strip_suffix("---"))
+            .map(str::trim)
+            .ok_or_else(|| format!("Expected a '--- CASE name ---' line on line {}, found '{line}'.", i + 1))?;
+
+        let name = match name {
+            "" => format!("case {}", cases.len() + 1),
+            name => name.to_owned()
+        };
+
+        if cases.iter().any(|case| case.name == name) {
+            return Err(format!("Duplicate case '{name}' on line {}.", i + 1));
+        }
+
+        let mut expected = [None, None];
+
+        while let Some((i, line)) = lines.next_if(|(_, line)| line.starts_with("--- PART ")) {
+            let malformed = || format!("Malformed answer on line {}: '{line}' (expected '--- PART ONE: ANSWER ---'.)", i + 1);
+
+            let (part, answer) = line
+                .trim_end()
+                .strip_prefix("--- PART ")
+                .and_then(|rest| rest.strip_suffix(" ---"))
+                .and_then(|rest| rest.split_once(": "))
+                .ok_or_else(malformed)?;
+
+            let part = match part {
+                "ONE" => Part::One,
+                "TWO" => Part::Two,
+                _ => return Err(malformed())
+            };
+
+            expected[part.flag() as usize] = Some(answers::unescape(answer));
+        }
+
+        let mut input = String::new();
+
+        while let Some((_, line)) = lines.next_if(|(_, line)| !line.starts_with(CASE)) {
+            input.push_str(line);
+            input.push('\n');
+        }
+
+        // Blank lines around the input are just there for readability.
+        let input = format!("{}\n", input.trim_start_matches(['\n', '\r']).trim_end_matches(['\n', '\r']));
+
+        cases.push(Case { name, input, expected });
+    }
+
+    Ok(cases)
+}
+
+/// Load a year's example file for `day`.
+pub fn load(year: &Year, day: u8) -> Result<String, String> {
+    let path = year.path(format!("src/inputs/test_{day:02}.txt"));
+
+    std::fs::read_to_string(&path)
+        .map_err(|err| format!("Could not read {}: {err}", path.display()))
+}
+
+/// Pick the input `Solver::load_test` should use for `part` out of a multi-case file: that of
+/// the first case with an answer for it, or of the first case if none have one.
+pub fn select(cases: &[Case], part: Part) -> Option<&Case> {
+    cases
+        .iter()
+        .find(|case| case.expected(part).is_some())
+        .or_else(|| cases.first())
+}
+
+/// Run one case of a day's example file and assert that it produces the expected answer.
+///
+/// This is what the tests generated by the [`build`](crate::build) step call.
+pub fn check(year: &Year, day: u8, case: &str, part: Part) {
+    let text = load(year, day).unwrap_or_else(|err| panic!("{err}"));
+    let cases = parse(&text).unwrap_or_else(|err| panic!("Example file for day {day} is malformed: {err}"));
+
+    let case = cases
+        .iter()
+        .find(|c| c.name == case)
+        .unwrap_or_else(|| panic!("Day {day} has no example case '{case}'."));
+
+    let expected = case
+        .expected(part)
+        .unwrap_or_else(|| panic!("Case '{}' has no expected answer for part {part}.", case.name));
+
+    let solve = year
+        .day(day)
+        .unwrap_or_else(|| panic!("Day {day} is not registered for {}.", year.year))
+        .solve;
+
+    params::set_mode(Mode::Example);
+    let outcome = solve(&case.input, &[part]);
+
+    if let Some(diagnostic) = outcome.parse_error {
+        panic!("Case '{}' failed to parse:\n{diagnostic}", case.name);
+    }
+
+    match &outcome.parts[0].answer {
+        Answer::Solved(answer) => assert_eq!(answer, expected, "Wrong answer for case '{}', part {part}.", case.name),
+        answer => panic!("Case '{}', part {part}: {answer}", case.name)
+    }
+}
+
+/// Turn a case name into something usable as part of a test function's name.
+pub fn ident(name: &str) -> String {
+    let mut ident = String::with_capacity(name.len());
+
+    for c in name.chars() {
+        match c.is_ascii_alphanumeric() {
+            true => ident.push(c.to_ascii_lowercase()),
+            false if !ident.ends_with('_') => ident.push('_'),
+            false => ()
+        }
+    }
+
+    ident.trim_matches('_').to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "\
+--- CASE first ---
+--- PART ONE: 7 ---
+--- PART TWO: 19 ---
+mjqjpqmgbljsphdztnvjfqwrcgsmlb
+
+--- CASE ---
+--- PART TWO: a\\nb ---
+
+  indented
+second line
+";
+
+    #[test]
+    fn parsing() {
+        let cases = parse(FILE).unwrap();
+
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].name, "first");
+        assert_eq!(cases[0].input, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n");
+        assert_eq!(cases[0].expected(Part::One), Some("7"));
+        assert_eq!(cases[1].name, "case 2");
+        assert_eq!(cases[1].input, "  indented\nsecond line\n");
+        assert_eq!(cases[1].expected(Part::One), None);
+        assert_eq!(cases[1].expected(Part::Two), Some("a\nb"));
+
+        assert_eq!(select(&cases, Part::Two).unwrap().name, "first");
+        assert!(has_cases(FILE));
+        assert!(!has_cases("mjqjpqmgbljsphdztnvjfqwrcgsmlb"));
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(parse("mjqj\n--- CASE a ---\n").is_err());
+        assert!(parse("--- CASE a ---\n--- PART THREE: 1 ---\n").is_err());
+        assert!(parse("--- CASE a ---\n--- PART ONE 1 ---\n").is_err());
+        assert!(parse("--- CASE a ---\nx\n--- CASE a ---\ny\n").is_err());
+    }
+
+    #[test]
+    fn idents() {
+        assert_eq!(ident("first"), "first");
+        assert_eq!(ident("Larger example (part 2)"), "larger_example_part_2");
+        assert_eq!(ident("case 2"), "case_2");
+    }
+}
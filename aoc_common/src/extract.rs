@@ -0,0 +1,257 @@
+//! Pulling examples (and their answers) out of a saved puzzle page.
+//!
+//! Copying examples into `test_NN.txt` by hand is error prone, so the `extract` command reads
+//! a puzzle page saved from the browser instead. On its own it lists the page's `<pre><code>`
+//! blocks; with `--block N` it writes the chosen block(s) to `src/inputs/test_NN.txt` as a
+//! [multi-case example file](crate::examples).
+//!
+//! The expected answers are guessed from the emphasized code (`<code><em>...</em></code>`)
+//! in each part's `<article>`: the example's answer is almost always the last one mentioned.
+//! They're only attached to the first chosen block, and are worth double checking either way.
+
+use std::path::Path;
+
+use crate::{
+    answers,
+    cli::Options,
+    runner::{Part, Year}
+};
+
+/// Decode the handful of entities that show up in puzzle pages.
+fn unescape_html(text: &str) -> String {
+    text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The text of an HTML fragment, with any tags removed and entities decoded.
+fn text_of(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => ()
+        }
+    }
+
+    unescape_html(&text)
+}
+
+/// Every section of `html` that starts with `open` and ends with `close` (exclusive of both.)
+fn sections<'h>(html: &'h str, open: &str, close: &str) -> Vec<&'h str> {
+    let mut found = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find(open) {
+        let after = &rest[start + open.len()..];
+
+        let Some(end) = after.find(close) else {
+            break
+        };
+
+        found.push(&after[..end]);
+        rest = &after[end + close.len()..];
+    }
+
+    found
+}
+
+/// The contents of every `<pre><code>` block on the page, in order.
+pub fn code_blocks(html: &str) -> Vec<String> {
+    sections(html, "<pre><code>", "</code></pre>")
+        .into_iter()
+        .map(text_of)
+        .collect()
+}
+
+/// The emphasized code in each part's description, in order.
+pub fn emphasized(html: &str) -> Vec<Vec<String>> {
+    sections(html, "<article", "</article>")
+        .into_iter()
+        .map(|article| {
+            let mut found = sections(article, "<code><em>", "</em></code>");
+            found.extend(sections(article, "<em><code>", "</code></em>"));
+
+            // Pages use both forms, so put them back in the order they appear.
+            found.sort_by_key(|section| section.as_ptr() as usize - article.as_ptr() as usize);
+            found.into_iter().map(text_of).collect()
+        })
+        .collect()
+}
+
+/// Render the chosen blocks as a multi-case example file, attaching `expected` to the first.
+pub fn render(blocks: &[&str], expected: &[Option<String>; 2]) -> String {
+    let mut file = String::new();
+
+    for (i, block) in blocks.iter().enumerate() {
+        match blocks.len() {
+            1 => file += "--- CASE example ---\n",
+            _ => file += &format!("--- CASE example {} ---\n", i + 1)
+        }
+
+        if i == 0 {
+            for (part, answer) in Part::ALL.iter().zip(expected) {
+                if let Some(answer) = answer {
+                    let name = match part {
+                        Part::One => "ONE",
+                        Part::Two => "TWO"
+                    };
+
+                    file += &format!("--- PART {name}: {} ---\n", answers::escape(answer));
+                }
+            }
+        }
+
+        file += block.trim_end_matches('\n');
+        file += "\n";
+    }
+
+    file
+}
+
+/// Parse a comma separated list of 1-based block numbers.
+pub fn parse_blocks(value: &str) -> Result<Vec<usize>, String> {
+    value
+        .split(',')
+        .map(|n| match n.trim().parse::<usize>() {
+            Ok(n) if n > 0 => Ok(n),
+            _ => Err(format!("'{n}' is not a valid block number (they start at 1.)"))
+        })
+        .collect()
+}
+
+/// List the code blocks of a saved puzzle page, or write the chosen ones to the day's example file.
+pub fn extract(year: &Year, options: &Options) -> Result<(), String> {
+    let day = match &options.days {
+        Some(days) if days.start() == days.end() => *days.start(),
+        _ => return Err("The extract command needs exactly one day (e.g. extract 7 --page day7.html).".to_owned())
+    };
+
+    let page = options
+        .page
+        .as_ref()
+        .ok_or("The extract command needs a saved puzzle page (--page PATH).")?;
+
+    let html = std::fs::read_to_string(page)
+        .map_err(|err| format!("Could not read {}: {err}", page.display()))?;
+
+    let blocks = code_blocks(&html);
+    let emphasized = emphasized(&html);
+
+    if blocks.is_empty() {
+        return Err(format!("{} has no <pre><code> blocks.", page.display()));
+    }
+
+    // The last emphasized value in each part's description.
+    let expected: [Option<String>; 2] = [0, 1].map(|i| emphasized.get(i).and_then(|e| e.last().cloned()));
+
+    if options.blocks.is_empty() {
+        for (i, block) in blocks.iter().enumerate() {
+            let preview: Vec<_> = block.lines().take(3).collect();
+            println!("[{}] {} line(s): {}", i + 1, block.lines().count(), preview.join(" / "));
+        }
+
+        for (part, values) in Part::ALL.iter().zip(&emphasized) {
+            println!("Part {part} emphasizes: {}", values.join(", "));
+        }
+
+        println!("Choose block(s) with --block N (e.g. --block 1 or --block 1,3).");
+        return Ok(());
+    }
+
+    let chosen = options.blocks
+        .iter()
+        .map(|n| {
+            blocks
+                .get(n - 1)
+                .map(String::as_str)
+                .ok_or_else(|| format!("There is no block {n} (the page has {}.)", blocks.len()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let path = year.path(Path::new(&format!("src/inputs/test_{day:02}.txt")));
+
+    std::fs::write(&path, render(&chosen, &expected))
+        .map_err(|err| format!("Could not write {}: {err}", path.display()))?;
+
+    println!("Wrote {} case(s) to {}.", chosen.len(), path.display());
+
+    for (part, answer) in Part::ALL.iter().zip(&expected) {
+        match answer {
+            Some(answer) => println!("Expecting {} for part {part}; check that it's the example's answer.", answers::escape(answer)),
+            None => println!("Found no answer for part {part}.")
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 18: Boiling Boulders ---</h2>
+<p>For example, if the scan were:</p>
+<pre><code>1,1,1
+2,1,1
+</code></pre>
+<p>... the total surface area is <code><em>10</em></code>. Here's a larger example:</p>
+<pre><code>2,2,2
+1,2,2
+</code></pre>
+<p>In this larger example, the total surface area is <code><em>64</em></code>.</p>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Only <em>exterior</em> sides count; here the area is <code><em>58</em></code>.</p>
+<pre><code>&lt;tag&gt; &amp; <em>friends</em></code></pre>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn blocks_and_answers() {
+        let blocks = code_blocks(PAGE);
+
+        assert_eq!(blocks, ["1,1,1\n2,1,1\n", "2,2,2\n1,2,2\n", "<tag> & friends"]);
+        assert_eq!(emphasized(PAGE), [vec!["10", "64"], vec!["58"]]);
+    }
+
+    #[test]
+    fn mixed_emphasis() {
+        let page = "<article><p>First <em><code>1</code></em>, then <code><em>2</em></code>, \
+                    then <em><code>3</code></em> and finally <code><em>4</em></code>.</p></article>";
+
+        assert_eq!(emphasized(page), [vec!["1", "2", "3", "4"]]);
+    }
+
+    #[test]
+    fn rendering() {
+        let file = render(&["2,2,2\n1,2,2\n"], &[Some("64".to_owned()), Some("58".to_owned())]);
+        assert_eq!(file, "--- CASE example ---\n--- PART ONE: 64 ---\n--- PART TWO: 58 ---\n2,2,2\n1,2,2\n");
+
+        let file = render(&["1,1,1", "2,2,2"], &[Some("64".to_owned()), None]);
+        let cases = examples::parse(&file).unwrap();
+
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].expected(Part::One), Some("64"));
+        assert_eq!(cases[1].expected(Part::One), None);
+        assert_eq!(cases[1].input, "2,2,2\n");
+    }
+
+    #[test]
+    fn block_numbers() {
+        assert_eq!(parse_blocks("2"), Ok(vec![2]));
+        assert_eq!(parse_blocks("1, 3"), Ok(vec![1, 3]));
+        assert!(parse_blocks("0").is_err());
+        assert!(parse_blocks("x").is_err());
+    }
+}
@@ -0,0 +1,399 @@
+//! Downloading real inputs from the Advent of Code website.
+//!
+//! Inputs are fetched with the session cookie of a logged in browser, which is looked up
+//! from the `AOC_SESSION` environment variable or a `.aoc_session` file in the workspace
+//! (found the same way as the [input key](crate::inputs).) Downloaded inputs are cached
+//! as `src/inputs/NN.txt`, and a day is never fetched again once it has either a plain
+//! or an encrypted input.
+//!
+//! The actual HTTP requests go through the [`Http`] trait. [`Curl`] is the real backend
+//! (the site is HTTPS only, which `std` can't speak), while [`PlainHttp`] is a minimal
+//! client for plain `http://` URLs, used by the tests to talk to a local stub server.
+//! [Submitting answers](crate::submit) goes through the same plumbing.
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    path::Path,
+    process::{Command, Stdio},
+    time::Duration
+};
+
+use crate::{
+    cli::Options,
+    inputs,
+    runner::{self, Year}
+};
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const SESSION_FILE: &str = ".aoc_session";
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const USER_AGENT: &str = "aoc_common (github.com/SomewhereOutInSpace)";
+
+/// How long to wait for the server before giving up.
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A response to an HTTP request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Why an HTTP request didn't produce a response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HttpError {
+    /// The server couldn't be reached at all (no network, DNS failure, refused connection...)
+    Offline(String),
+    Other(String)
+}
+
+impl std::fmt::Display for HttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HttpError::Offline(reason) => write!(f, "could not reach the server; are you offline? ({reason})"),
+            HttpError::Other(reason) => write!(f, "{reason}")
+        }
+    }
+}
+
+/// Something that can make (cookie authenticated) HTTP requests.
+pub trait Http {
+    /// Send a GET request, or a POST of the URL-encoded `form` if there is one.
+    fn request(&self, url: &str, session: &str, form: Option<&str>) -> Result<Response, HttpError>;
+
+    fn get(&self, url: &str, session: &str) -> Result<Response, HttpError> {
+        self.request(url, session, None)
+    }
+
+    fn post(&self, url: &str, session: &str, form: &str) -> Result<Response, HttpError> {
+        self.request(url, session, Some(form))
+    }
+}
+
+/// Makes requests by running the `curl` executable.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Curl;
+
+impl Http for Curl {
+    fn request(&self, url: &str, session: &str, form: Option<&str>) -> Result<Response, HttpError> {
+        // The cookie goes through a config on stdin, rather than showing up in the process list.
+        let mut config = format!("header = \"Cookie: session={session}\"\n");
+
+        if let Some(form) = form {
+            config += &format!("data = \"{form}\"\n");
+        }
+
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--config", "-", "--max-time"])
+            .arg(TIMEOUT.as_secs().to_string())
+            .args(["--user-agent", USER_AGENT, "--write-out", "\n%{http_code}", url])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| HttpError::Other(format!("could not run curl ({err})")))?;
+
+        child
+            .stdin
+            .take()
+            .expect("curl's stdin is piped.")
+            .write_all(config.as_bytes())
+            .map_err(|err| HttpError::Other(format!("could not talk to curl ({err})")))?;
+
+        let output = child
+            .wait_with_output()
+            .map_err(|err| HttpError::Other(format!("curl failed ({err})")))?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+
+        match output.status.code() {
+            Some(0) => (),
+            // Couldn't resolve host, couldn't connect, timed out.
+            Some(6 | 7 | 28) => return Err(HttpError::Offline(stderr)),
+            _ => return Err(HttpError::Other(format!("curl failed ({stderr})")))
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout
+            .rsplit_once('\n')
+            .ok_or_else(|| HttpError::Other("malformed output from curl".to_owned()))?;
+
+        let status = status
+            .trim()
+            .parse()
+            .map_err(|_| HttpError::Other(format!("malformed status code from curl '{status}'")))?;
+
+        Ok(Response { status, body: body.to_owned() })
+    }
+}
+
+/// A bare-bones HTTP/1.1 client over a `TcpStream`, for plain `http://` URLs only.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlainHttp;
+
+impl Http for PlainHttp {
+    fn request(&self, url: &str, session: &str, form: Option<&str>) -> Result<Response, HttpError> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| HttpError::Other(format!("'{url}' is not a plain http:// URL")))?;
+
+        let (host, path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, "/")
+        };
+
+        let mut stream = TcpStream::connect(host).map_err(|err| HttpError::Offline(err.to_string()))?;
+        let _ = stream.set_read_timeout(Some(TIMEOUT));
+
+        let mut request = format!(
+            "{} {path} HTTP/1.1\r\nHost: {host}\r\nUser-Agent: {USER_AGENT}\r\nCookie: session={session}\r\nConnection: close\r\n",
+            if form.is_some() { "POST" } else { "GET" }
+        );
+
+        match form {
+            Some(form) => request += &format!(
+                "Content-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n\r\n{form}",
+                form.len()
+            ),
+            None => request += "\r\n"
+        }
+
+        let mut raw = Vec::new();
+
+        stream
+            .write_all(request.as_bytes())
+            .and_then(|_| stream.read_to_end(&mut raw))
+            .map_err(|err| HttpError::Other(format!("request failed ({err})")))?;
+
+        let raw = String::from_utf8_lossy(&raw);
+        let malformed = || HttpError::Other("malformed response".to_owned());
+
+        let (head, body) = raw.split_once("\r\n\r\n").ok_or_else(malformed)?;
+        let status = head
+            .split(' ')
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .ok_or_else(malformed)?;
+
+        Ok(Response { status, body: body.to_owned() })
+    }
+}
+
+/// Look up the session cookie for the year crate at `root`.
+pub fn session(root: &Path) -> Result<String, String> {
+    if let Ok(session) = std::env::var(SESSION_VAR) {
+        return Ok(session.trim().to_owned());
+    }
+
+    let path = inputs::find_upwards(root, SESSION_FILE).ok_or_else(|| format!(
+        "No session cookie to talk to the website with; set {SESSION_VAR} or create a {SESSION_FILE} file in the workspace."
+    ))?;
+
+    std::fs::read_to_string(&path)
+        .map(|session| session.trim().to_owned())
+        .map_err(|err| format!("Could not read {}: {err}", path.display()))
+}
+
+/// What happened when fetching a day's input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded,
+    /// The day already had an input, so nothing was requested.
+    Cached
+}
+
+/// Downloads inputs into a year crate's `src/inputs/`.
+pub struct Fetcher<H> {
+    pub http: H,
+    pub base_url: String,
+    pub session: String,
+}
+
+impl Fetcher<Curl> {
+    /// A fetcher for the real website, using the session cookie for the year crate at `root`.
+    pub fn new(root: &Path) -> Result<Self, String> {
+        Ok(Self {
+            http: Curl,
+            base_url: BASE_URL.to_owned(),
+            session: session(root)?,
+        })
+    }
+}
+
+impl<H: Http> Fetcher<H> {
+    /// Make sure the year crate at `root` has an input for `day` of `year`, downloading it if not.
+    pub fn fetch(&self, root: &Path, year: u16, day: u8) -> Result<Fetched, String> {
+        if inputs::exists(root, day) {
+            return Ok(Fetched::Cached);
+        }
+
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+
+        let response = self.http
+            .get(&url, &self.session)
+            .map_err(|err| format!("Could not fetch the input for day {day} of {year}: {err}."))?;
+
+        match response.status {
+            200 if !response.body.trim().is_empty() => (),
+            200 => return Err(format!("The input for day {day} of {year} came back empty.")),
+            404 => return Err(format!("Day {day} of {year} isn't unlocked yet.")),
+            400 | 500 => return Err("The session cookie was rejected; it has probably expired.".to_owned()),
+            status => return Err(format!("Fetching {url} failed with HTTP status {status}."))
+        }
+
+        let (plain, _) = inputs::paths(root, day);
+
+        std::fs::create_dir_all(plain.parent().expect("Input paths have a parent."))
+            .and_then(|_| std::fs::write(&plain, response.body))
+            .map_err(|err| format!("Could not write {}: {err}", plain.display()))?;
+
+        Ok(Fetched::Downloaded)
+    }
+}
+
+/// Fetch the inputs of the selected days (or of every registered day) that aren't cached yet.
+pub fn fetch_inputs(year: &Year, options: &Options) -> Result<(), String> {
+    let root = Path::new(year.root);
+    let fetcher = Fetcher::new(root)?;
+
+    let days: Vec<u8> = match &options.days {
+        Some(days) => days.clone().collect(),
+        None => runner::select_days(year, options)?
+            .iter()
+            .map(|day| day.day)
+            .collect()
+    };
+
+    let mut failures = 0;
+
+    for day in days {
+        let (plain, _) = inputs::paths(root, day);
+
+        match fetcher.fetch(root, year.year, day) {
+            Ok(Fetched::Downloaded) => println!("Day {day:02}: downloaded {}", year.path(&plain).display()),
+            Ok(Fetched::Cached) => println!("Day {day:02}: already have an input"),
+            Err(err) => {
+                failures += 1;
+                println!("Day {day:02}: {err}");
+            }
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        n => Err(format!("{n} input(s) could not be fetched."))
+    }
+}
+
+/// A local stand-in for the website, for testing against.
+#[cfg(test)]
+pub(crate) mod stub {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex}
+    };
+
+    /// Serve canned responses on a local port, one per connection, returning its URL and a log
+    /// of the requests it receives (the head, then the body after a blank line.)
+    pub fn server(responses: Vec<&'static str>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&requests);
+
+        std::thread::spawn(move || {
+            for (stream, response) in listener.incoming().zip(responses) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut lines = Vec::new();
+                let mut length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if line.trim().is_empty() {
+                        break;
+                    }
+
+                    if let Some(value) = line.strip_prefix("Content-Length: ") {
+                        length = value.trim().parse().unwrap();
+                    }
+
+                    lines.push(line.trim().to_owned());
+                }
+
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+
+                log.lock().unwrap().push(format!("{}\n\n{}", lines.join("\n"), String::from_utf8_lossy(&body)));
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (url, requests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::TcpListener,
+        path::PathBuf
+    };
+
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc_common_fetch_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn fetches_once() {
+        let (url, requests) = stub::server(vec![
+            "HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\n1721\n979\n",
+            "HTTP/1.1 404 Not Found\r\n\r\nPlease don't repeatedly request this endpoint before it unlocks!",
+        ]);
+
+        let root = temp_root("once");
+        let fetcher = Fetcher { http: PlainHttp, base_url: url, session: "cookie".to_owned() };
+
+        assert_eq!(fetcher.fetch(&root, 2020, 1), Ok(Fetched::Downloaded));
+        assert_eq!(std::fs::read_to_string(root.join("src/inputs/01.txt")).unwrap(), "1721\n979\n");
+        assert_eq!(fetcher.fetch(&root, 2020, 1), Ok(Fetched::Cached));
+
+        assert!(fetcher.fetch(&root, 2020, 2).unwrap_err().contains("isn't unlocked yet"));
+        assert!(!root.join("src/inputs/02.txt").exists());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("GET /2020/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("Cookie: session=cookie"));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn offline() {
+        // Grab a free port, then close it again so that nothing is listening there.
+        let url = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            format!("http://{}", listener.local_addr().unwrap())
+        };
+
+        let root = temp_root("offline");
+        let fetcher = Fetcher { http: PlainHttp, base_url: url, session: "cookie".to_owned() };
+
+        let err = fetcher.fetch(&root, 2020, 1).unwrap_err();
+        assert!(err.contains("are you offline?"), "{err}");
+        assert!(!root.join("src/inputs/01.txt").exists());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut}
+};
+
+use crate::Direction;
+
+/// A dense, row-major 2D grid.
+///
+/// Positions are `(row, col)` pairs, with `(0, 0)` in the top-left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn from_square(data: &[T], dim: usize) -> Self {
+        assert_eq!(data.len(), dim * dim, "Grid data is not a {dim}x{dim} square.");
+
+        Self {
+            data: data.to_vec(),
+            rows: dim,
+            cols: dim,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Build a grid out of a list of rows, which must all be the same length.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Self {
+        let mut data = Vec::new();
+        let mut count = 0;
+        let mut cols = None;
+
+        for row in rows {
+            let width = row.len();
+            assert_eq!(*cols.get_or_insert(width), width, "Grid row {count} has the wrong length.");
+
+            data.extend(row);
+            count += 1;
+        }
+
+        Self {
+            data,
+            rows: count,
+            cols: cols.unwrap_or(0),
+        }
+    }
+
+    /// Parse a grid out of puzzle text, mapping each character through `f`.
+    ///
+    /// Every line becomes a row; all lines must be the same length.
+    pub fn parse(puzzle: &str, mut f: impl FnMut(char) -> T) -> Self {
+        let mut data = Vec::with_capacity(puzzle.len());
+        let mut rows = 0;
+        let mut cols = None;
+
+        for line in puzzle.lines() {
+            let before = data.len();
+            data.extend(line.chars().map(&mut f));
+
+            let width = data.len() - before;
+            assert_eq!(*cols.get_or_insert(width), width, "Grid row {rows} has the wrong length.");
+            rows += 1;
+        }
+
+        Self {
+            data,
+            rows,
+            cols: cols.unwrap_or(0),
+        }
+    }
+
+    pub fn row(&self, index: usize) -> impl Iterator<Item = &T> {
+        self
+            .data
+            .iter()
+            .skip(index * self.cols)
+            .take(self.cols)
+    }
+
+    pub fn col(&self, index: usize) -> impl Iterator<Item = &T> {
+        self
+            .data
+            .iter()
+            .skip(index)
+            .step_by(self.cols.max(1))
+            .take(self.rows)
+    }
+
+    pub fn row_mut(&mut self, index: usize) -> &mut [T] {
+        &mut self.data[index * self.cols..(index + 1) * self.cols]
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if !self.contains(row, col) {
+            return None;
+        }
+
+        self.data.get(self.cols * row + col)
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if !self.contains(row, col) {
+            return None;
+        }
+
+        self.data.get_mut(self.cols * row + col)
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: T) {
+        if let Some(cell) = self.get_mut(row, col) {
+            *cell = value;
+        }
+    }
+
+    /// The dimensions of the grid, as `(rows, cols)`.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    pub fn is_on_edge(&self, row: usize, col: usize) -> bool {
+        row == 0 || col == 0 || row + 1 == self.rows || col + 1 == self.cols
+    }
+
+    /// Yields every position in the grid, in row-major order.
+    pub fn coordinates(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.rows)
+            .flat_map(|r| {
+                (0..self.cols)
+                    .map(move |c| (r, c))
+            })
+    }
+
+    /// Yields every position in the grid alongside its value, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.coordinates().zip(&self.data)
+    }
+
+    /// Find the position of the first cell (in row-major order) matching the predicate.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.data
+            .iter()
+            .position(&mut predicate)
+            .map(|i| (i / self.cols, i % self.cols))
+    }
+
+    /// Apply a signed `(row, col)` offset to a position, returning `None` if
+    /// the result would fall outside the grid.
+    pub fn offset(&self, (row, col): (usize, usize), (dr, dc): (isize, isize)) -> Option<(usize, usize)> {
+        let row = row.checked_add_signed(dr)?;
+        let col = col.checked_add_signed(dc)?;
+
+        self.contains(row, col).then_some((row, col))
+    }
+
+    /// The position one step away in the given direction, if it's inside the grid.
+    pub fn step(&self, position: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        self.offset(position, direction.delta())
+    }
+
+    /// Yields the (up to four) orthogonally adjacent positions that are inside the grid.
+    pub fn neighbors(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(position, dir))
+    }
+
+    /// Yields the (up to eight) adjacent positions that are inside the grid, diagonals included.
+    pub fn neighbors_diagonal(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (-1..=1)
+            .flat_map(|dr| (-1..=1).map(move |dc| (dr, dc)))
+            .filter(|delta| delta != &(0, 0))
+            .filter_map(move |delta| self.offset(position, delta))
+    }
+
+    /// Yields every element *past* the given position in a straight line
+    /// towards the edge of the grid, nearest first.
+    pub fn ray(&self, position: (usize, usize), direction: Direction) -> impl Iterator<Item = &T> + '_ {
+        std::iter::successors(
+            self.step(position, direction),
+            move |pos| self.step(*pos, direction)
+        )
+        .map(|(row, col)| &self[(row, col)])
+    }
+
+    /// Build a new grid of the same dimensions by mapping every cell through `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            data: self.data.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        self.get(row, col)
+            .unwrap_or_else(|| panic!("Position ({row}, {col}) is outside the grid."))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("Position ({row}, {col}) is outside the grid."))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.rows {
+            for cell in self.row(row) {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = "\
+        123\n\
+        456\n\
+        789";
+
+    #[test]
+    fn parse_and_display() {
+        let grid = Grid::parse(SQUARE, |c| c.to_digit(10).unwrap());
+
+        assert_eq!(grid.dimensions(), (3, 3));
+        assert_eq!(grid[(1, 2)], 6);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(format!("{grid}"), format!("{SQUARE}\n"));
+    }
+
+    const RECTANGLE: &str = "\
+        abcde\n\
+        fghij\n\
+        klmno";
+
+    #[test]
+    fn rectangular() {
+        let mut grid = Grid::parse(RECTANGLE, |c| c);
+
+        assert_eq!(grid.dimensions(), (3, 5));
+        assert_eq!(grid.row(1).collect::<String>(), "fghij");
+        assert_eq!(grid.col(4).collect::<String>(), "ejo");
+        assert_eq!(grid.get(2, 4), Some(&'o'));
+        assert_eq!(grid.get(4, 2), None);
+        assert_eq!(grid.position(|c| *c == 'h'), Some((1, 2)));
+        assert_eq!(grid.coordinates().last(), Some((2, 4)));
+        assert_eq!(grid.coordinates().count(), 15);
+
+        grid.set(2, 3, 'X');
+        grid.set(3, 2, 'Y');
+        assert_eq!(grid.row(2).collect::<String>(), "klmXo");
+        assert_eq!(format!("{grid}"), "abcde\nfghij\nklmXo\n");
+
+        let tall = Grid::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6]]);
+
+        assert_eq!(tall.dimensions(), (3, 2));
+        assert_eq!(tall.col(1).copied().collect::<Vec<_>>(), [2, 4, 6]);
+        assert_eq!(tall[(2, 0)], 5);
+        assert!(tall.is_on_edge(1, 1));
+        assert_eq!(tall.ray((0, 0), Direction::Down).copied().collect::<Vec<_>>(), [3, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn ragged() {
+        Grid::from_rows(vec![vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn offsets_and_neighbors() {
+        let grid = Grid::parse(SQUARE, |c| c);
+
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (2, 1)), Some((2, 1)));
+        assert_eq!(grid.step((2, 2), Direction::Right), None);
+        assert_eq!(grid.neighbors((0, 0)).count(), 2);
+        assert_eq!(grid.neighbors((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors_diagonal((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors_diagonal((2, 0)).count(), 3);
+    }
+
+    #[test]
+    fn rays() {
+        let grid = Grid::parse(SQUARE, |c| c);
+
+        assert_eq!(grid.ray((2, 1), Direction::Up).collect::<String>(), "52");
+        assert_eq!(grid.ray((1, 0), Direction::Right).collect::<String>(), "56");
+        assert_eq!(grid.ray((1, 0), Direction::Left).count(), 0);
+    }
+}
@@ -0,0 +1,280 @@
+//! Puzzle inputs, kept encrypted at rest.
+//!
+//! Real inputs are personal and shouldn't be redistributed, so only their encrypted forms
+//! (`src/inputs/NN.txt.enc`) are committed; the plain `NN.txt` files are ignored by git.
+//! [`read`] prefers a plain file if there is one (e.g. one that was just downloaded), and
+//! otherwise decrypts transparently with the local key, which is looked up in order from:
+//! - the `AOC_KEY` environment variable, as 64 hex digits;
+//! - the file named by the `AOC_KEY_FILE` environment variable;
+//! - a `.aoc_key` file in the year crate's directory or any of its parents (normally the
+//!   workspace root.)
+//!
+//! The `encrypt` command seals any plain inputs that are new or have changed, generating
+//! a key first if there isn't one yet. Keep a copy of it somewhere safe, and share it out of
+//! band with anyone else who needs the real inputs. Note that this only protects inputs from
+//! here on: any committed in plain before are still in the repository's history.
+//!
+//! Encrypted files are laid out as a magic header, a random nonce and the output of
+//! [`crypt::seal`].
+
+use std::{
+    io::{Read, Write},
+    path::{Path, PathBuf}
+};
+
+use crate::{
+    cli::Options,
+    crypt::{self, Key, KEY_LEN, NONCE_LEN},
+    runner::Year
+};
+
+pub const KEY_VAR: &str = "AOC_KEY";
+pub const KEY_FILE_VAR: &str = "AOC_KEY_FILE";
+pub const KEY_FILE: &str = ".aoc_key";
+
+const MAGIC: &[u8] = b"AOCENC1\n";
+
+/// Parse a key from its hex form.
+pub fn parse_key(hex: &str) -> Result<Key, String> {
+    let hex = hex.trim();
+    let invalid = || format!("Invalid key (expected {} hex digits.)", KEY_LEN * 2);
+
+    if hex.len() != KEY_LEN * 2 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+
+    let mut key = [0; KEY_LEN];
+
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+
+    Ok(key)
+}
+
+pub fn key_to_hex(key: &Key) -> String {
+    key.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Find a file called `name` in `root` or the nearest of its parents.
+pub(crate) fn find_upwards(root: &Path, name: &str) -> Option<PathBuf> {
+    root.ancestors()
+        .map(|dir| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Find the key file for the year crate at `root`, if there is one.
+fn find_key_file(root: &Path) -> Option<PathBuf> {
+    match std::env::var_os(KEY_FILE_VAR) {
+        Some(path) => Some(PathBuf::from(path)),
+        None => find_upwards(root, KEY_FILE)
+    }
+}
+
+/// Look up the key for the year crate at `root`.
+pub fn key(root: &Path) -> Result<Key, String> {
+    if let Ok(hex) = std::env::var(KEY_VAR) {
+        return parse_key(&hex).map_err(|err| format!("{KEY_VAR}: {err}"));
+    }
+
+    let Some(path) = find_key_file(root) else {
+        return Err(format!(
+            "No key to decrypt the inputs with; set {KEY_VAR}, or {KEY_FILE_VAR}, or create a {KEY_FILE} file in the workspace."
+        ));
+    };
+
+    let hex = std::fs::read_to_string(&path)
+        .map_err(|err| format!("Could not read {}: {err}", path.display()))?;
+
+    parse_key(&hex).map_err(|err| format!("{}: {err}", path.display()))
+}
+
+/// Fill a buffer with random bytes from the OS.
+///
+/// Falls back on the randomly seeded hasher behind `HashMap` where `/dev/urandom` doesn't exist.
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+
+    let from_os = std::fs::File::open("/dev/urandom")
+        .and_then(|mut file| file.read_exact(&mut bytes));
+
+    if from_os.is_err() {
+        use std::hash::{BuildHasher, Hasher};
+
+        let state = std::collections::hash_map::RandomState::new();
+
+        for (i, chunk) in bytes.chunks_mut(8).enumerate() {
+            let mut hasher = state.build_hasher();
+            hasher.write_usize(i);
+            hasher.write_u128(std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_nanos());
+            chunk.copy_from_slice(&hasher.finish().to_le_bytes()[..chunk.len()]);
+        }
+    }
+
+    bytes
+}
+
+pub fn generate_key() -> Key {
+    random_bytes()
+}
+
+/// Encrypt an input under a fresh nonce.
+pub fn encrypt(key: &Key, plaintext: &str) -> Vec<u8> {
+    let nonce = random_bytes::<NONCE_LEN>();
+
+    let mut data = MAGIC.to_vec();
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&crypt::seal(key, &nonce, plaintext.as_bytes()));
+    data
+}
+
+/// Decrypt the output of [`encrypt`].
+pub fn decrypt(key: &Key, data: &[u8]) -> Result<String, String> {
+    let data = data
+        .strip_prefix(MAGIC)
+        .filter(|data| data.len() >= NONCE_LEN)
+        .ok_or("not an encrypted input")?;
+
+    let (nonce, sealed) = data.split_at(NONCE_LEN);
+    let nonce = nonce.try_into().expect("Nonce has the wrong length.");
+
+    let plaintext = crypt::open(key, &nonce, sealed)
+        .ok_or("decryption failed (wrong key, or corrupted file)")?;
+
+    String::from_utf8(plaintext).map_err(|_| "decrypted input is not valid UTF-8".to_owned())
+}
+
+/// The plain and encrypted paths of a real input.
+pub fn paths(root: &Path, day: u8) -> (PathBuf, PathBuf) {
+    let plain = root.join(format!("src/inputs/{day:02}.txt"));
+    let encrypted = root.join(format!("src/inputs/{day:02}.txt.enc"));
+    (plain, encrypted)
+}
+
+/// Whether the year crate at `root` has an input for `day`, plain (and non-empty) or encrypted.
+pub fn exists(root: &Path, day: u8) -> bool {
+    let (plain, encrypted) = paths(root, day);
+
+    encrypted.is_file() || std::fs::metadata(plain).is_ok_and(|meta| meta.len() > 0)
+}
+
+/// Read the real input for `day` of the year crate at `root`, decrypting it if needed.
+pub fn read(root: &Path, day: u8) -> Result<String, String> {
+    let (plain, encrypted) = paths(root, day);
+
+    match std::fs::read_to_string(&plain) {
+        Ok(puzzle) if !puzzle.is_empty() => return Ok(puzzle),
+        Ok(_) => (),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => (),
+        Err(err) => return Err(format!("Could not read {}: {err}", plain.display()))
+    }
+
+    let data = match std::fs::read(&encrypted) {
+        Ok(data) => data,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(format!(
+                "No input for day {day} (neither {} nor {} exist); download it with the fetch command.",
+                plain.display(), encrypted.display()
+            ));
+        },
+        Err(err) => return Err(format!("Could not read {}: {err}", encrypted.display()))
+    };
+
+    decrypt(&key(root)?, &data).map_err(|err| format!("{}: {err}", encrypted.display()))
+}
+
+/// Write a new key file with owner-only permissions, refusing to replace an existing one.
+fn write_key_file(path: &Path, key: &Key) -> Result<(), String> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    options
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{}", key_to_hex(key)))
+        .map_err(|err| format!("Could not create {}: {err}", path.display()))
+}
+
+/// Encrypt the plain inputs of the selected days (or all of them) that are new or have changed.
+pub fn encrypt_inputs(year: &Year, options: &Options) -> Result<(), String> {
+    let root = Path::new(year.root);
+
+    let key = match key(root) {
+        Ok(key) => key,
+        Err(_) if std::env::var_os(KEY_VAR).is_none() && find_key_file(root).is_none() => {
+            let key = generate_key();
+            let path = root.parent().unwrap_or(root).join(KEY_FILE);
+
+            write_key_file(&path, &key)?;
+            println!("Generated a new key in {}; keep a copy of it somewhere safe.", year.path(&path).display());
+            key
+        },
+        Err(err) => return Err(err)
+    };
+
+    let mut encrypted = 0;
+
+    for day in 1..=25 {
+        if options.days.as_ref().is_some_and(|days| !days.contains(&day)) {
+            continue;
+        }
+
+        let (plain, sealed) = paths(root, day);
+
+        let puzzle = match std::fs::read_to_string(&plain) {
+            Ok(puzzle) if !puzzle.is_empty() => puzzle,
+            Ok(_) => continue,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(format!("Could not read {}: {err}", plain.display()))
+        };
+
+        // Leave unchanged inputs alone, so that re-running this doesn't churn the repository.
+        let unchanged = std::fs::read(&sealed)
+            .ok()
+            .and_then(|data| decrypt(&key, &data).ok())
+            .is_some_and(|existing| existing == puzzle);
+
+        if unchanged {
+            continue;
+        }
+
+        std::fs::write(&sealed, encrypt(&key, &puzzle))
+            .map_err(|err| format!("Could not write {}: {err}", sealed.display()))?;
+
+        println!("Encrypted {}", year.path(&sealed).display());
+        encrypted += 1;
+    }
+
+    println!("Encrypted {encrypted} input(s) for {}.", year.year);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        let key = generate_key();
+
+        assert_eq!(parse_key(&key_to_hex(&key)), Ok(key));
+        assert_eq!(parse_key(&format!("  {}\n", key_to_hex(&key))), Ok(key));
+        assert!(parse_key("abcd").is_err());
+        assert!(parse_key(&"zz".repeat(KEY_LEN)).is_err());
+        assert_ne!(generate_key(), key);
+    }
+
+    #[test]
+    fn round_trip() {
+        let key = generate_key();
+        let data = encrypt(&key, "1721\n979\n");
+
+        assert!(data.starts_with(MAGIC));
+        assert_ne!(encrypt(&key, "1721\n979\n"), data);
+        assert_eq!(decrypt(&key, &data).as_deref(), Ok("1721\n979\n"));
+        assert!(decrypt(&generate_key(), &data).is_err());
+        assert!(decrypt(&key, b"1721\n979\n").is_err());
+    }
+}
@@ -0,0 +1,46 @@
+#![warn(clippy::perf, clippy::style, warnings)]
+
+//! Utilities shared between all the year crates.
+//!
+//! Anything that more than one day ends up reinventing - 2D grids, points,
+//! cardinal directions and so on - belongs in here instead.
+
+mod direction;
+mod grid;
+mod point;
+
+pub mod answers;
+pub mod bench;
+pub mod build;
+pub mod cli;
+pub mod crypt;
+pub mod cycle;
+pub mod examples;
+pub mod extract;
+pub mod fetch;
+pub mod inputs;
+pub mod meta;
+pub mod params;
+pub mod parse;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod stats;
+pub mod submit;
+pub mod watch;
+
+#[cfg(feature = "viz")]
+pub mod viz;
+
+pub use direction::Direction;
+pub use grid::Grid;
+pub use point::Point;
+
+/// Glob-import to bring all the shared types into scope.
+pub mod prelude {
+    pub use crate::{Direction, Grid, Point};
+    pub use crate::params::Param;
+    pub use crate::parse::{OrRaise, ParseError, ParseResult, Source};
+    pub use crate::{cycle, search};
+}
@@ -0,0 +1,266 @@
+//! Metadata about each day, parsed from the header of its module docs.
+//!
+//! Day modules start with a header in a fixed form:
+//! ``` text
+//! //! # Day 7 - No Space Left On Device
+//! //!
+//! //! Puzzle opened on time.
+//! //! - P1 completed @ 03:53:12 (18848)
+//! //! - P2 completed @ 04:06:23 (17989)
+//! ```
+//! ... giving the puzzle's title, whether it was started as soon as it unlocked (optionally with
+//! a reason if it wasn't) and, for each part, how long after unlocking it was solved along with
+//! the resulting global rank. Anything after the header is free-form and ignored.
+//!
+//! Fields that are missing or left blank (as in the `day_n.rs` templates) parse as `None`.
+
+use std::{fmt::Display, path::Path, time::Duration};
+
+use crate::runner::Part;
+
+/// When a part was completed, relative to the puzzle unlocking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SolveTime {
+    Within(Duration),
+    /// The leaderboard stops recording times after a day.
+    OverDay
+}
+
+impl SolveTime {
+    pub const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// The time, with anything past a day counted as exactly one.
+    pub fn capped(&self) -> Duration {
+        match self {
+            SolveTime::Within(time) => (*time).min(Self::DAY),
+            SolveTime::OverDay => Self::DAY
+        }
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+
+        if text.starts_with('>') {
+            return Some(SolveTime::OverDay);
+        }
+
+        let mut fields = text.split(':').map(|field| field.parse::<u64>().ok());
+
+        match (fields.next()??, fields.next()??, fields.next()??, fields.next()) {
+            (h, m, s, None) if m < 60 && s < 60 => Some(SolveTime::Within(Duration::from_secs(h * 3600 + m * 60 + s))),
+            _ => None
+        }
+    }
+}
+
+impl Display for SolveTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveTime::Within(time) => {
+                let secs = time.as_secs();
+                write!(f, "{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+            },
+            SolveTime::OverDay => write!(f, ">24h")
+        }
+    }
+}
+
+/// One part's entry in the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Completion {
+    pub time: SolveTime,
+    /// The global rank, when it was noted down.
+    pub rank: Option<u32>,
+}
+
+impl Completion {
+    /// Parse what follows "completed @", e.g. `02:13:26 (19716)` or `>24h`.
+    fn parse(text: &str) -> Option<Self> {
+        let (time, rank) = match text.trim_end().strip_suffix(')').and_then(|rest| rest.rsplit_once('(')) {
+            Some((time, rank)) => (time, rank.trim().parse().ok()),
+            None => (text, None)
+        };
+
+        // Allow for a comment (or emoji) between the time and the rank.
+        let time = SolveTime::parse(time.split_whitespace().next().unwrap_or(""))
+            .or_else(|| SolveTime::parse(time))?;
+
+        Some(Self { time, rank })
+    }
+}
+
+/// Whether the puzzle was started as soon as it unlocked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Opened {
+    OnTime,
+    Late {
+        reason: Option<String>
+    }
+}
+
+/// Everything the header of a day's module says about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayMeta {
+    pub day: u8,
+    pub title: Option<String>,
+    pub opened: Option<Opened>,
+    pub parts: [Option<Completion>; 2],
+}
+
+impl DayMeta {
+    /// Parse the header out of a day module's source.
+    pub fn parse(day: u8, source: &str) -> Self {
+        let mut meta = Self { day, title: None, opened: None, parts: [None, None] };
+
+        let lines = source
+            .lines()
+            .map_while(|line| line.trim().strip_prefix("//!"))
+            .map(str::trim);
+
+        for line in lines {
+            if line.is_empty() {
+                // A blank line after the completion times ends the header.
+                match meta.opened.is_some() || meta.parts.iter().any(Option::is_some) {
+                    true => break,
+                    false => continue
+                }
+            }
+
+            if let Some(heading) = line.strip_prefix("# ") {
+                meta.title = heading
+                    .split_once(" - ")
+                    .map(|(_, title)| title.trim())
+                    .or(heading.trim().strip_suffix(" -").map(|_| ""))
+                    .filter(|title| !title.is_empty())
+                    .map(str::to_owned);
+            } else if let Some(rest) = line.strip_prefix("Puzzle opened ") {
+                meta.opened = match rest.strip_prefix("on time") {
+                    Some(_) => Some(Opened::OnTime),
+                    None => rest.strip_prefix("late").map(|reason| Opened::Late { reason: parse_reason(reason) })
+                };
+            } else if let Some(rest) = line.strip_prefix("- P") {
+                let index = match rest.chars().next() {
+                    Some('1') => 0,
+                    Some('2') => 1,
+                    _ => break
+                };
+
+                if let Some((_, completion)) = rest.split_once('@') {
+                    meta.parts[index] = Completion::parse(completion);
+                }
+            } else if meta.title.is_some() {
+                // The end of the header.
+                break;
+            }
+        }
+
+        meta
+    }
+
+    /// Read the header of `day_NN.rs` in `src_dir`.
+    pub fn load(src_dir: &Path, day: u8) -> Result<Self, String> {
+        let path = src_dir.join(format!("day_{day:02}.rs"));
+
+        std::fs::read_to_string(&path)
+            .map(|source| Self::parse(day, &source))
+            .map_err(|err| format!("Could not read {}: {err}", path.display()))
+    }
+
+    pub fn part(&self, part: Part) -> Option<&Completion> {
+        self.parts[part.flag() as usize].as_ref()
+    }
+
+    /// Whether both parts were noted down as completed.
+    pub fn complete(&self) -> bool {
+        self.parts.iter().all(Option::is_some)
+    }
+}
+
+/// The reason following "Puzzle opened late", e.g. ` (had an exam.)` or ` - got sick.`
+fn parse_reason(text: &str) -> Option<String> {
+    let text = text.trim().trim_start_matches(['-', '.']).trim();
+
+    let text = match text.strip_prefix('(').and_then(|text| text.rsplit_once(')')) {
+        Some((inner, _)) => inner,
+        None => text
+    };
+
+    let text = text.trim().trim_end_matches('.');
+
+    match text.is_empty() {
+        true => None,
+        false => Some(text.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hms(h: u64, m: u64, s: u64) -> SolveTime {
+        SolveTime::Within(Duration::from_secs(h * 3600 + m * 60 + s))
+    }
+
+    #[test]
+    fn headers() {
+        let source = "//! # Day 7 - No Space Left On Device\n\
+                      //! \n\
+                      //! Puzzle opened on time. \n\
+                      //! - P1 completed @ 03:53:12 (18848)\n\
+                      //! - P2 completed @ 20:15:24 🥴 (17989)\n\
+                      //! \n\
+                      //! - P1 completed @ 00:00:01 (1) is not part of the header.\n\
+                      \n\
+                      use super::*;\n";
+
+        assert_eq!(DayMeta::parse(7, source), DayMeta {
+            day: 7,
+            title: Some("No Space Left On Device".to_owned()),
+            opened: Some(Opened::OnTime),
+            parts: [
+                Some(Completion { time: hms(3, 53, 12), rank: Some(18848) }),
+                Some(Completion { time: hms(20, 15, 24), rank: Some(17989) })
+            ]
+        });
+
+        let source = "//! # Day 16 - Proboscidea Volcanium\n\
+                      //! Puzzle opened late (had an exam in the morning.)\n\
+                      //! - P1 completed @ >24h (14280)\n\
+                      //! - P2 completed @ > 24h\n";
+
+        let meta = DayMeta::parse(16, source);
+
+        assert_eq!(meta.opened, Some(Opened::Late { reason: Some("had an exam in the morning".to_owned()) }));
+        assert_eq!(meta.part(Part::One), Some(&Completion { time: SolveTime::OverDay, rank: Some(14280) }));
+        assert_eq!(meta.part(Part::Two), Some(&Completion { time: SolveTime::OverDay, rank: None }));
+        assert!(meta.complete());
+    }
+
+    #[test]
+    fn blank_headers() {
+        let template = "//! # Day N - \n//! \n//! Puzzle opened on time. \n//! - P1 completed @ \n//! - P2 completed @\n";
+        let meta = DayMeta::parse(1, template);
+
+        assert_eq!(meta.title, None);
+        assert_eq!(meta.parts, [None, None]);
+        assert!(!meta.complete());
+
+        let meta = DayMeta::parse(2, "//! Puzzle opened late. \n");
+        assert_eq!(meta.opened, Some(Opened::Late { reason: None }));
+
+        let meta = DayMeta::parse(17, "//! # Day 17 - \n//! Puzzle opened late - I got sick :(\n");
+        assert_eq!(meta.opened, Some(Opened::Late { reason: Some("I got sick :(".to_owned()) }));
+
+        assert_eq!(DayMeta::parse(3, "use super::*;\n"), DayMeta { day: 3, title: None, opened: None, parts: [None, None] });
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(SolveTime::parse("02:13:26"), Some(hms(2, 13, 26)));
+        assert_eq!(SolveTime::parse("2:61:00"), None);
+        assert_eq!(SolveTime::parse("soon"), None);
+        assert_eq!(hms(0, 5, 9).to_string(), "00:05:09");
+        assert_eq!(SolveTime::OverDay.to_string(), ">24h");
+        assert!(hms(23, 59, 59) < SolveTime::OverDay);
+    }
+}
@@ -0,0 +1,157 @@
+//! Per-day puzzle parameters that differ between the example and the real input.
+//!
+//! Some puzzles quietly change their constants for the example - "row 10" instead of
+//! "row 2000000" and so on. Rather than swapping these with `#[cfg(test)]`, a day declares
+//! them as [`Param`]s with both values:
+//! ``` ignore
+//! const TARGET_ROW: Param<i64> = Param::new(DAY_15, "target_row", 10, 2_000_000);
+//!
+//! fn part_one(input: &Self::Input<'_>) -> Self::Output {
+//!     let target_row = TARGET_ROW.get();
+//!     // ...
+//! }
+//! ```
+//! Which value [`Param::get`] returns depends on the current [`Mode`], which the year crate's
+//! `Solver::load` and `Solver::load_test` implementations set just before handing over the
+//! puzzle text. Any value can also be overridden at runtime from the command line with
+//! `--param DAY.NAME=VALUE` (e.g. `--param 15.target_row=11`.)
+
+use std::{
+    cell::Cell,
+    collections::BTreeMap,
+    fmt::Debug,
+    str::FromStr,
+    sync::Mutex
+};
+
+/// Which kind of input is currently being solved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    Example,
+    Real
+}
+
+thread_local! {
+    // Thread-local so that tests (which run in parallel) can't trample each other.
+    static MODE: Cell<Mode> = const { Cell::new(Mode::Real) };
+}
+
+static OVERRIDES: Mutex<BTreeMap<(u8, String), String>> = Mutex::new(BTreeMap::new());
+
+/// Set the [`Mode`] for the current thread.
+pub fn set_mode(mode: Mode) {
+    MODE.with(|cell| cell.set(mode))
+}
+
+/// The [`Mode`] of the current thread.
+pub fn mode() -> Mode {
+    MODE.with(Cell::get)
+}
+
+/// Override the value of a day's parameter for every thread, regardless of mode.
+pub fn set_override(day: u8, name: &str, value: &str) {
+    OVERRIDES
+        .lock()
+        .unwrap()
+        .insert((day, name.to_owned()), value.to_owned());
+}
+
+/// Parse and apply an override in the command line form `DAY.NAME=VALUE`.
+pub fn apply_override(arg: &str) -> Result<(), String> {
+    let malformed = || format!("Malformed parameter override '{arg}' (expected DAY.NAME=VALUE.)");
+
+    let (key, value) = arg.split_once('=').ok_or_else(malformed)?;
+    let (day, name) = key.split_once('.').ok_or_else(malformed)?;
+    let day = day.parse::<u8>().map_err(|_| malformed())?;
+
+    if name.is_empty() {
+        return Err(malformed());
+    }
+
+    set_override(day, name, value);
+    Ok(())
+}
+
+fn lookup_override(day: u8, name: &str) -> Option<String> {
+    OVERRIDES
+        .lock()
+        .unwrap()
+        .get(&(day, name.to_owned()))
+        .cloned()
+}
+
+/// A named puzzle parameter with separate values for the example and real inputs.
+#[derive(Debug, Clone, Copy)]
+pub struct Param<T> {
+    day: u8,
+    name: &'static str,
+    example: T,
+    real: T,
+}
+
+impl<T> Param<T> {
+    pub const fn new(day: u8, name: &'static str, example: T, real: T) -> Self {
+        Self { day, name, example, real }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T: Copy> Param<T> {
+    /// A parameter that's the same for the example and the real input, but that
+    /// can still be overridden from the command line.
+    pub const fn fixed(day: u8, name: &'static str, value: T) -> Self {
+        Self::new(day, name, value, value)
+    }
+}
+
+impl<T> Param<T> where
+    T: Copy + FromStr,
+    T::Err: Debug
+{
+    /// Resolve the parameter's value: the override if there is one, otherwise
+    /// the value for the current thread's [`Mode`].
+    pub fn get(&self) -> T {
+        if let Some(value) = lookup_override(self.day, self.name) {
+            return value
+                .parse::<T>()
+                .unwrap_or_else(|err| panic!(
+                    "Invalid override '{value}' for parameter {}.{}: {err:?}",
+                    self.day, self.name
+                ))
+        }
+
+        match mode() {
+            Mode::Example => self.example,
+            Mode::Real => self.real
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Day 0 never exists, so overrides here can't leak into real solutions.
+    const SIZE: Param<u32> = Param::new(0, "size", 7, 70);
+    const ROUNDS: Param<u32> = Param::fixed(0, "rounds", 20);
+
+    #[test]
+    fn modes_and_overrides() {
+        set_mode(Mode::Example);
+        assert_eq!(SIZE.get(), 7);
+
+        set_mode(Mode::Real);
+        assert_eq!(SIZE.get(), 70);
+        assert_eq!(ROUNDS.get(), 20);
+
+        apply_override("0.rounds=3").unwrap();
+        assert_eq!(ROUNDS.get(), 3);
+
+        assert!(apply_override("rounds=3").is_err());
+        assert!(apply_override("x.rounds=3").is_err());
+        assert!(apply_override("0.=3").is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! Parse errors that point at the offending part of the puzzle input.
+//!
+//! A day's parser is written as a fallible function over a [`Source`], which knows the day
+//! and the full puzzle text. Because every token handed to it is a slice of that text, an error
+//! can be traced back to its line and column without any bookkeeping in the parser itself:
+//! ``` ignore
+//! fn parse_input(src: Source<'_>) -> ParseResult<Vec<(u64, u64)>> {
+//!     src.lines()
+//!         .map(|line| {
+//!             let (l, r) = src.split_once(line, ',')?;
+//!             Ok((src.parse(l)?, src.parse(r)?))
+//!         })
+//!         .collect()
+//! }
+//! ```
+//! `lib_aoc`'s `Solution::parse` has to return the input itself, so the error is raised as
+//! a panic at that boundary with [`OrRaise::or_raise`]. The runner catches it, prints the
+//! diagnostic, and moves on to the next day.
+
+use std::{
+    fmt::Display,
+    str::FromStr
+};
+
+use crate::Grid;
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// A malformed piece of puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line number, or 0 if the offending text couldn't be located.
+    pub line: usize,
+    /// 1-based column (in characters) of the start of the offending text.
+    pub column: usize,
+    /// The offending text (only its first line, if it spans several.)
+    pub text: String,
+    /// The full line containing the offending text.
+    pub context: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Abort parsing with this error.
+    ///
+    /// The error is used as the panic payload, so that the runner can tell it apart from bugs.
+    pub fn raise(self) -> ! {
+        std::panic::panic_any(self)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.line == 0 {
+            return write!(f, "day {}: {}", self.day, self.message);
+        }
+
+        writeln!(f, "day {}, line {}, column {}: {}", self.day, self.line, self.column, self.message)?;
+
+        let gutter = self.line.to_string().len();
+        let width = self.text.chars().count().max(1);
+
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.context)?;
+        write!(f, "{:gutter$} | {:pad$}{}", "", "", "^".repeat(width), pad = self.column - 1)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Unwraps a [`ParseResult`] by [raising](ParseError::raise) the error, if there is one.
+pub trait OrRaise<T> {
+    fn or_raise(self) -> T;
+}
+
+impl<T> OrRaise<T> for ParseResult<T> {
+    fn or_raise(self) -> T {
+        self.unwrap_or_else(|err| err.raise())
+    }
+}
+
+/// A day's puzzle text, for building [`ParseError`]s that point into it.
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'i> {
+    day: u8,
+    puzzle: &'i str,
+}
+
+impl<'i> Source<'i> {
+    pub fn new(day: u8, puzzle: &'i str) -> Self {
+        Self { day, puzzle }
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn puzzle(&self) -> &'i str {
+        self.puzzle
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'i> {
+        self.puzzle.lines()
+    }
+
+    /// An error pointing at `text`, which should be a slice of the puzzle.
+    ///
+    /// Text that isn't a slice of the puzzle is located by searching for it instead.
+    pub fn error(&self, text: &str, message: impl Display) -> ParseError {
+        let start = self.puzzle.as_ptr() as usize;
+        let ptr = text.as_ptr() as usize;
+
+        let offset = match ptr >= start && ptr + text.len() <= start + self.puzzle.len() {
+            true => Some(ptr - start),
+            false => self.puzzle.find(text).filter(|_| !text.is_empty())
+        };
+
+        let text = text.lines().next().unwrap_or_default().to_owned();
+        let message = message.to_string();
+
+        let Some(offset) = offset else {
+            return ParseError { day: self.day, line: 0, column: 0, text, context: String::new(), message };
+        };
+
+        let before = &self.puzzle[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.puzzle[offset..].find('\n').map_or(self.puzzle.len(), |i| offset + i);
+
+        ParseError {
+            day: self.day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text,
+            context: self.puzzle[line_start..line_end].trim_end_matches('\r').to_owned(),
+            message,
+        }
+    }
+
+    /// An error pointing just past the end of the puzzle.
+    pub fn eof(&self, message: impl Display) -> ParseError {
+        self.error(&self.puzzle[self.puzzle.trim_end().len()..], message)
+    }
+
+    /// Parse `text` (a slice of the puzzle) using its [`FromStr`] implementation.
+    pub fn parse<T>(&self, text: &'i str) -> ParseResult<T> where
+        T: FromStr,
+        T::Err: Display
+    {
+        text.parse::<T>().map_err(|err| {
+            let name = std::any::type_name::<T>()
+                .rsplit("::")
+                .next()
+                .unwrap_or_default();
+
+            self.error(text, format!("couldn't parse '{text}' as {name} ({err})"))
+        })
+    }
+
+    /// Split `text` on the first occurrence of `delimiter`, or fail pointing at `text`.
+    pub fn split_once(&self, text: &'i str, delimiter: impl Delimiter) -> ParseResult<(&'i str, &'i str)> {
+        delimiter
+            .split_once(text)
+            .ok_or_else(|| self.error(text, format!("expected {}", delimiter.describe())))
+    }
+
+    /// Strip `prefix` from the start of `text`, or fail pointing at `text`.
+    pub fn strip_prefix(&self, text: &'i str, prefix: &str) -> ParseResult<&'i str> {
+        text
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error(text, format!("expected {prefix:?}")))
+    }
+
+    /// Map every character of `text` with `cell`, failing at the first one it rejects.
+    pub fn chars<T>(&self, text: &'i str, mut cell: impl FnMut(char) -> Option<T>) -> ParseResult<Vec<T>> {
+        text
+            .char_indices()
+            .map(|(i, c)| {
+                cell(c).ok_or_else(|| self.error(&text[i..i + c.len_utf8()], format!("unexpected character {c:?}")))
+            })
+            .collect()
+    }
+
+    /// Parse the whole puzzle as a rectangular [`Grid`], mapping each character with `cell`.
+    pub fn grid<T>(&self, mut cell: impl FnMut(char) -> Option<T>) -> ParseResult<Grid<T>> {
+        let mut rows = Vec::new();
+
+        for line in self.lines() {
+            let row = self.chars(line, &mut cell)?;
+
+            if let Some(first) = rows.first().map(Vec::len).filter(|len| *len != row.len()) {
+                return Err(self.error(line, format!("expected {first} columns, found {}", row.len())));
+            }
+
+            rows.push(row);
+        }
+
+        if rows.is_empty() {
+            return Err(self.eof("expected a grid, found an empty input"));
+        }
+
+        Ok(Grid::from_rows(rows))
+    }
+}
+
+/// Something [`Source::split_once`] can split on.
+pub trait Delimiter: Copy {
+    fn split_once<'a>(&self, text: &'a str) -> Option<(&'a str, &'a str)>;
+    fn describe(&self) -> String;
+}
+
+impl Delimiter for char {
+    fn split_once<'a>(&self, text: &'a str) -> Option<(&'a str, &'a str)> {
+        text.split_once(*self)
+    }
+
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+impl Delimiter for &str {
+    fn split_once<'a>(&self, text: &'a str) -> Option<(&'a str, &'a str)> {
+        text.split_once(*self)
+    }
+
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "2-4,6-8\n2-3,4x5\n";
+
+    #[test]
+    fn location() {
+        let src = Source::new(4, PUZZLE);
+        let line = src.lines().nth(1).unwrap();
+        let (_, right) = src.split_once(line, ',').unwrap();
+
+        let err = src.split_once(right, '-').unwrap_err();
+
+        assert_eq!((err.day, err.line, err.column), (4, 2, 5));
+        assert_eq!(err.text, "4x5");
+        assert_eq!(err.context, "2-3,4x5");
+        assert_eq!(
+            err.to_string(),
+            "day 4, line 2, column 5: expected '-'\n  |\n2 | 2-3,4x5\n  |     ^^^"
+        );
+    }
+
+    #[test]
+    fn parse() {
+        let src = Source::new(4, PUZZLE);
+
+        assert_eq!(src.parse::<u8>(&PUZZLE[2..3]), Ok(4));
+
+        let err = src.parse::<u8>(&PUZZLE[12..15]).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert!(err.message.starts_with("couldn't parse '4x5' as u8"));
+
+        let err = src.chars(&PUZZLE[8..15], |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "-"));
+    }
+
+    #[test]
+    fn grid() {
+        let src = Source::new(8, "303\n255\n");
+        assert_eq!(src.grid(|c| c.to_digit(10)).unwrap().dimensions(), (2, 3));
+
+        let src = Source::new(8, "303\n25\n");
+        let err = src.grid(|c| c.to_digit(10)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "expected 3 columns, found 2");
+
+        assert!(Source::new(8, "").grid(|c| c.to_digit(10)).is_err());
+    }
+}
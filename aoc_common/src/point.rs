@@ -0,0 +1,115 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Sub, SubAssign}
+};
+
+use crate::Direction;
+
+/// A point on an unbounded 2D plane.
+///
+/// Like [`Direction`], this uses screen coordinates - `y` grows *downwards*.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_dist(&self, other: &Self) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The point one step away in the given direction.
+    pub fn step(&self, direction: Direction) -> Self {
+        let (dy, dx) = direction.delta();
+        self.offset(dx as i64, dy as i64)
+    }
+
+    pub fn offset(&self, dx: i64, dy: i64) -> Self {
+        Self {
+            x: self.x + dx,
+            y: self.y + dy
+        }
+    }
+
+    /// The four orthogonally adjacent points, in [`Direction::ALL`] order.
+    pub fn neighbors(&self) -> [Self; 4] {
+        Direction::ALL.map(|dir| self.step(dir))
+    }
+
+    /// All eight adjacent points, diagonals included.
+    pub fn neighbors_diagonal(&self) -> [Self; 8] {
+        [
+            self.offset(-1, -1),
+            self.offset(0, -1),
+            self.offset(1, -1),
+            self.offset(-1, 0),
+            self.offset(1, 0),
+            self.offset(-1, 1),
+            self.offset(0, 1),
+            self.offset(1, 1)
+        ]
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.offset(rhs.x, rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.offset(-rhs.x, -rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps() {
+        let p = Point::new(3, -2);
+
+        assert_eq!(p.step(Direction::Up), Point::new(3, -3));
+        assert_eq!(p.step(Direction::Right), Point::new(4, -2));
+        assert_eq!(p.manhattan_dist(&Point::ORIGIN), 5);
+        assert_eq!(p - p, Point::ORIGIN);
+        assert_eq!(format!("{p}"), "(3, -2)");
+    }
+}
@@ -0,0 +1,332 @@
+//! Machine-readable output for runner results.
+//!
+//! Every part of every day becomes one [`Record`], written out either as a line of
+//! JSON (JSON-lines) or as a row of CSV, so that runs can be aggregated and diffed by scripts.
+
+use std::{
+    fmt::Write as _,
+    io::{self, Write}
+};
+
+use crate::runner::{Answer, Outcome, Part, Pretty};
+
+/// How the runner should print outcomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// Human-readable output, in the same layout as `lib_aoc`.
+    #[default]
+    Pretty,
+    /// One JSON object per line.
+    Json,
+    /// Comma-separated values, with a header row.
+    Csv
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pretty" => Ok(Format::Pretty),
+            "json" | "jsonl" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("'{s}' is not a valid format (expected pretty, json or csv.)"))
+        }
+    }
+}
+
+/// The result of one part of one day, flattened for output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    /// `solved`, `unimplemented`, `panicked` or `parse_error`.
+    pub status: &'static str,
+    /// The answer, or the panic message/parse diagnostic if the part failed.
+    pub answer: Option<String>,
+    pub parse_ns: u128,
+    pub solve_ns: u128,
+}
+
+impl Record {
+    pub const HEADER: &'static str = "year,day,part,status,answer,parse_ns,solve_ns";
+
+    /// Flatten an [`Outcome`] into one record per part.
+    pub fn from_outcome(year: u16, outcome: &Outcome) -> Vec<Self> {
+        outcome.parts
+            .iter()
+            .map(|part| {
+                let (status, answer) = match &part.answer {
+                    Answer::Solved(answer) => ("solved", Some(answer.clone())),
+                    Answer::Unimplemented => ("unimplemented", None),
+                    Answer::Panicked(message) => ("panicked", Some(message.clone())),
+                    Answer::Unparsed => ("parse_error", outcome.parse_error.clone())
+                };
+
+                Record {
+                    year,
+                    day: outcome.day,
+                    part: part.part,
+                    status,
+                    answer,
+                    parse_ns: outcome.parse_time.as_nanos(),
+                    solve_ns: part.time.as_nanos(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        let answer = match &self.answer {
+            Some(answer) => json_string(answer),
+            None => "null".to_owned()
+        };
+
+        format!(
+            r#"{{"year":{},"day":{},"part":{},"status":"{}","answer":{answer},"parse_ns":{},"solve_ns":{}}}"#,
+            self.year, self.day, self.part, self.status, self.parse_ns, self.solve_ns
+        )
+    }
+
+    /// Parse a record back out of the output of [`Record::to_json`].
+    pub fn from_json(line: &str) -> Option<Self> {
+        let mut fields = line.trim().strip_prefix('{')?.strip_suffix('}')?;
+        let mut record = Record {
+            year: 0,
+            day: 0,
+            part: Part::One,
+            status: "",
+            answer: None,
+            parse_ns: 0,
+            solve_ns: 0,
+        };
+
+        while !fields.is_empty() {
+            let (key, rest) = fields.strip_prefix('"')?.split_once("\":")?;
+
+            let (value, rest) = match rest.strip_prefix('"') {
+                Some(rest) => {
+                    let (value, rest) = parse_json_string(rest)?;
+                    (Some(value), rest)
+                },
+                None => {
+                    let end = rest.find(',').unwrap_or(rest.len());
+                    let raw = &rest[..end];
+                    (raw.ne("null").then(|| raw.to_owned()), &rest[end..])
+                }
+            };
+
+            match (key, value) {
+                ("year", Some(value)) => record.year = value.parse().ok()?,
+                ("day", Some(value)) => record.day = value.parse().ok()?,
+                ("part", Some(value)) => record.part = match value.as_str() {
+                    "1" => Part::One,
+                    "2" => Part::Two,
+                    _ => return None
+                },
+                ("status", Some(value)) => {
+                    record.status = ["solved", "unimplemented", "panicked", "parse_error"]
+                        .into_iter()
+                        .find(|status| *status == value)?
+                },
+                ("answer", value) => record.answer = value,
+                ("parse_ns", Some(value)) => record.parse_ns = value.parse().ok()?,
+                ("solve_ns", Some(value)) => record.solve_ns = value.parse().ok()?,
+                _ => return None
+            }
+
+            fields = rest.strip_prefix(',').unwrap_or(rest);
+        }
+
+        Some(record)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let answer = self.answer
+            .as_deref()
+            .map(csv_field)
+            .unwrap_or_default();
+
+        format!(
+            "{},{},{},{},{answer},{},{}",
+            self.year, self.day, self.part, self.status, self.parse_ns, self.solve_ns
+        )
+    }
+}
+
+/// Writes outcomes to a sink in the chosen [`Format`].
+pub struct Reporter<W: Write> {
+    format: Format,
+    year: u16,
+    sink: W,
+    header_written: bool,
+}
+
+impl<W: Write> Reporter<W> {
+    pub fn new(format: Format, year: u16, sink: W) -> Self {
+        Self {
+            format,
+            year,
+            sink,
+            header_written: false,
+        }
+    }
+
+    pub fn report(&mut self, outcome: &Outcome) -> io::Result<()> {
+        match self.format {
+            Format::Pretty => write!(self.sink, "{}", Pretty(outcome))?,
+            Format::Json => {
+                for record in Record::from_outcome(self.year, outcome) {
+                    writeln!(self.sink, "{}", record.to_json())?;
+                }
+            },
+            Format::Csv => {
+                if !self.header_written {
+                    writeln!(self.sink, "{}", Record::HEADER)?;
+                    self.header_written = true;
+                }
+
+                for record in Record::from_outcome(self.year, outcome) {
+                    writeln!(self.sink, "{}", record.to_csv())?;
+                }
+            }
+        }
+
+        self.sink.flush()
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            },
+            c => escaped.push(c)
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+/// Parse the rest of a JSON string (after its opening quote), returning it and what follows it.
+fn parse_json_string(text: &str) -> Option<(String, &str)> {
+    let mut value = String::new();
+    let mut chars = text.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &text[i + 1..])),
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                'r' => value.push('\r'),
+                't' => value.push('\t'),
+                'u' => {
+                    let hex: String = (0..4).filter_map(|_| chars.next().map(|(_, c)| c)).collect();
+                    value.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                },
+                c => value.push(c)
+            },
+            c => value.push(c)
+        }
+    }
+
+    None
+}
+
+fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::runner::PartOutcome;
+
+    fn outcome() -> Outcome {
+        Outcome {
+            day: 10,
+            parse_time: Duration::from_nanos(1500),
+            parse_error: None,
+            parts: vec![
+                PartOutcome {
+                    part: Part::One,
+                    answer: Answer::Solved("13360".to_owned()),
+                    time: Duration::from_nanos(20),
+                },
+                PartOutcome {
+                    part: Part::Two,
+                    answer: Answer::Solved("\n█ \"a,b\"\n".to_owned()),
+                    time: Duration::from_nanos(30),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn json() {
+        let mut out = Vec::new();
+        Reporter::new(Format::Json, 2022, &mut out).report(&outcome()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"year\":2022,\"day\":10,\"part\":1,\"status\":\"solved\",\"answer\":\"13360\",\"parse_ns\":1500,\"solve_ns\":20}\n\
+             {\"year\":2022,\"day\":10,\"part\":2,\"status\":\"solved\",\"answer\":\"\\n█ \\\"a,b\\\"\\n\",\"parse_ns\":1500,\"solve_ns\":30}\n"
+        );
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut outcome = outcome();
+        outcome.parts[0].answer = Answer::Panicked("oh no\u{1}".to_owned());
+
+        for record in Record::from_outcome(2022, &outcome) {
+            assert_eq!(Record::from_json(&record.to_json()), Some(record));
+        }
+
+        assert_eq!(Record::from_json("Compiling aoc_2022"), None);
+        assert_eq!(Record::from_json("{\"year\":2022,\"status\":\"fine\"}"), None);
+    }
+
+    #[test]
+    fn csv() {
+        let mut out = Vec::new();
+        let mut reporter = Reporter::new(Format::Csv, 2022, &mut out);
+        reporter.report(&outcome()).unwrap();
+        reporter.report(&Outcome::new(11)).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "year,day,part,status,answer,parse_ns,solve_ns\n\
+             2022,10,1,solved,13360,1500,20\n\
+             2022,10,2,solved,\"\n█ \"\"a,b\"\"\n\",1500,30\n"
+        );
+    }
+
+    #[test]
+    fn missing_answers() {
+        let mut outcome = outcome();
+        outcome.parts[0].answer = Answer::Unimplemented;
+
+        let record = &Record::from_outcome(2024, &outcome)[0];
+
+        assert_eq!(record.answer, None);
+        assert!(record.to_json().contains("\"status\":\"unimplemented\",\"answer\":null"));
+        assert_eq!(record.to_csv(), "2024,10,1,unimplemented,,1500,20");
+    }
+}
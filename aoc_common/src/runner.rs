@@ -0,0 +1,587 @@
+//! Command line runner for the solutions of each year.
+//!
+//! `lib_aoc`'s `solve_through!` always loads the real input and runs every day from 1 to N.
+//! This module replaces it with a dispatch table of [`Day`]s (generated by the [`days!`](crate::days)
+//! macro) that can be run selectively, according to the [`Options`] parsed from the command line.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt::Display,
+    io::{IsTerminal, Read},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    time::{Duration, Instant}
+};
+
+use crate::{
+    answers,
+    bench,
+    cli::{Command, Input, Options},
+    examples,
+    extract,
+    fetch,
+    inputs,
+    meta::DayMeta,
+    params::{self, Mode},
+    parse::ParseError,
+    report::Reporter,
+    scaffold,
+    stats,
+    submit,
+    watch
+};
+
+/// One half of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// The equivalent `lib_aoc` part flag (`PART_ONE` is `false`, `PART_TWO` is `true`.)
+    pub fn flag(&self) -> bool {
+        matches!(self, Part::Two)
+    }
+
+    pub fn from_flag(flag: bool) -> Self {
+        match flag {
+            false => Part::One,
+            true => Part::Two
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2")
+        }
+    }
+}
+
+/// A single registered day, as generated by [`days!`](crate::days).
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    /// Parse the puzzle, then run the given parts against it.
+    pub solve: fn(&str, &[Part]) -> Outcome,
+}
+
+/// Everything the runner needs to know about a year crate.
+pub struct Year {
+    pub year: u16,
+    /// The year crate's directory (its `CARGO_MANIFEST_DIR`), which the other paths are relative to.
+    pub root: &'static str,
+    pub days: &'static [Day],
+    /// The crate's `Solver::load` implementation.
+    pub load: fn(u8) -> String,
+    /// The crate's `Solver::load_test` implementation.
+    pub load_test: fn(u8, bool) -> String,
+    /// Path to the year's [answers file](crate::answers).
+    pub answers: &'static str,
+}
+
+impl Year {
+    pub fn day(&self, day: u8) -> Option<&Day> {
+        self.days.iter().find(|d| d.day == day)
+    }
+
+    /// The [metadata](crate::meta) in the header of the day's module.
+    pub fn meta(&self, day: u8) -> Result<DayMeta, String> {
+        DayMeta::load(&Path::new(self.root).join("src"), day)
+    }
+
+    /// Resolve a path relative to the year crate.
+    ///
+    /// The result is kept relative to the working directory when it's inside the workspace,
+    /// so that it reads well in messages.
+    pub fn path(&self, relative: impl AsRef<Path>) -> PathBuf {
+        let path = Path::new(self.root).join(relative);
+
+        match std::env::current_dir() {
+            Ok(cwd) => path
+                .strip_prefix(&cwd)
+                .map(Path::to_path_buf)
+                .unwrap_or(path),
+            Err(_) => path
+        }
+    }
+}
+
+/// The standard `Solver::load`: reads the real input for `day` from the year's [input store](crate::inputs),
+/// first [downloading](crate::fetch) it if it's missing and a session cookie is set up.
+pub fn load(year: &Year, day: u8) -> String {
+    params::set_mode(Mode::Real);
+
+    let root = Path::new(year.root);
+
+    if !inputs::exists(root, day) {
+        if let Ok(fetcher) = fetch::Fetcher::new(root) {
+            fetcher
+                .fetch(root, year.year, day)
+                .unwrap_or_else(|err| panic!("{err}"));
+        }
+    }
+
+    inputs::read(root, day).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// The standard `Solver::load_test`: reads the example from the year's `src/inputs/test_NN.txt`.
+///
+/// Examples that differ between parts are either stored in one file separated by a `--- PART END ---`
+/// line, or as [multiple cases](crate::examples).
+pub fn load_test(year: &Year, day: u8, part: bool) -> String {
+    params::set_mode(Mode::Example);
+
+    let puzzle = std::fs::read_to_string(Path::new(year.root).join(format!("src/inputs/test_{day:02}.txt")))
+        .expect("Puzzle input could not be read.");
+
+    if examples::has_cases(&puzzle) {
+        let cases = examples::parse(&puzzle)
+            .unwrap_or_else(|err| panic!("Example file for day {day} is malformed: {err}"));
+
+        return examples::select(&cases, Part::from_flag(part))
+            .map(|case| case.input.clone())
+            .unwrap_or_default();
+    }
+
+    match puzzle.split_once("--- PART END ---") {
+        Some((one, two)) => match part {
+            false => one.trim().to_owned(),
+            true => two.trim().to_owned()
+        },
+        None => puzzle
+    }
+}
+
+/// The result of running one part of a solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Solved(String),
+    Unimplemented,
+    Panicked(String),
+    /// Not run, because the input failed to parse.
+    Unparsed
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Solved(answer) => write!(f, "{answer}"),
+            Answer::Unimplemented => write!(f, "unimplemented"),
+            Answer::Panicked(message) => write!(f, "panicked ({message})"),
+            Answer::Unparsed => write!(f, "not run (parse error)")
+        }
+    }
+}
+
+/// The answer and timing of one part of a solution.
+#[derive(Debug, Clone)]
+pub struct PartOutcome {
+    pub part: Part,
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+/// The product of running (some of) a day's solution.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub day: u8,
+    pub parse_time: Duration,
+    /// The diagnostic, if parsing failed (and so no parts were run.)
+    pub parse_error: Option<String>,
+    pub parts: Vec<PartOutcome>,
+}
+
+impl Outcome {
+    pub fn new(day: u8) -> Self {
+        Self {
+            day,
+            parse_time: Duration::ZERO,
+            parse_error: None,
+            parts: Vec::with_capacity(2),
+        }
+    }
+
+    /// Time the parsing step of a solution, catching any [`ParseError`] (or other panic) it raises.
+    pub fn time_parse<T>(&mut self, parse: impl FnOnce() -> T) -> Option<T> {
+        let start = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(parse));
+        self.parse_time += start.elapsed();
+
+        match result {
+            Ok(input) => Some(input),
+            Err(payload) => {
+                let diagnostic = match payload.downcast::<ParseError>() {
+                    Ok(err) => err.to_string(),
+                    Err(payload) => {
+                        let message = panic_message(&*payload).unwrap_or_else(|| "unknown payload".to_owned());
+                        format!("parser panicked ({message})")
+                    }
+                };
+
+                self.parse_error = Some(diagnostic);
+                None
+            }
+        }
+    }
+
+    /// Mark parts as not run, after parsing failed.
+    pub fn skip(&mut self, parts: &[Part]) {
+        for part in parts {
+            self.parts.push(PartOutcome {
+                part: *part,
+                answer: Answer::Unparsed,
+                time: Duration::ZERO,
+            });
+        }
+    }
+
+    /// Time one part of a solution, catching any panics it raises.
+    ///
+    /// `lib_aoc`'s default `part_one`/`part_two` implementations panic with a
+    /// non-string payload, so those are reported as [`Answer::Unimplemented`].
+    pub fn time_part<T: Display>(&mut self, part: Part, solve: impl FnOnce() -> T) {
+        let start = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(solve));
+        let time = start.elapsed();
+
+        let answer = match result {
+            Ok(answer) => Answer::Solved(answer.to_string()),
+            Err(payload) => match panic_message(&*payload) {
+                Some(message) => Answer::Panicked(message),
+                None => Answer::Unimplemented
+            }
+        };
+
+        self.parts.push(PartOutcome { part, answer, time });
+    }
+
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|p| p.time).sum::<Duration>()
+    }
+
+    fn merge(&mut self, other: Outcome) {
+        self.parse_time += other.parse_time;
+        self.parse_error = self.parse_error.take().or(other.parse_error);
+        self.parts.extend(other.parts);
+    }
+}
+
+/// Generates a `[Day; N]` dispatch table.
+///
+/// In order, the parameters are:
+/// - Your solution type.
+/// - Either the day to register through (registering every day in `1..=N`), or a
+///   `;` followed by a list of the days to register.
+///
+/// Days must be integer literals due to macro limitations. The list form is what the
+/// [`build`](crate::build) step generates.
+///
+/// The expansion refers to `::lib_aoc` directly, so the invoking crate must depend on it.
+#[macro_export]
+macro_rules! days {
+    (@day $sols:ty, $day:literal) => {
+        $crate::runner::Day {
+            day: $day,
+            solve: |puzzle, parts| {
+                let mut outcome = $crate::runner::Outcome::new($day);
+                let Some(input) = outcome.time_parse(|| {
+                    <$sols as ::lib_aoc::Solution<$day>>::parse(puzzle)
+                }) else {
+                    outcome.skip(parts);
+                    return outcome;
+                };
+
+                for part in parts {
+                    match part {
+                        $crate::runner::Part::One => outcome.time_part(*part, || {
+                            <$sols as ::lib_aoc::Solution<$day>>::part_one(&input)
+                        }),
+                        $crate::runner::Part::Two => outcome.time_part(*part, || {
+                            <$sols as ::lib_aoc::Solution<$day>>::part_two(&input)
+                        })
+                    }
+                }
+
+                outcome
+            }
+        }
+    };
+    ($sols:ty; $($day:literal),* $(,)?) => {
+        [
+            $( $crate::days!(@day $sols, $day), )*
+        ]
+    };
+    ($sols:ty, $up_to:literal) => {
+        ::lib_aoc::seq!(N in 1..=$up_to {
+            [
+                #( $crate::days!(@day $sols, N), )*
+            ]
+        })
+    };
+}
+
+/// Parse the process arguments and run the selected days of the selected year
+/// (the most recent one by default), exiting on a usage error.
+pub fn main(years: &[&Year]) {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{err}\n\n{}", crate::cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if options.help {
+        println!("{}", crate::cli::USAGE);
+        return;
+    }
+
+    // Stats cover every year unless one is selected.
+    if options.command == Command::Stats {
+        if let Err(err) = stats::stats(years, &options) {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+
+        return;
+    }
+
+    let year = match options.year {
+        Some(selected) => years.iter().find(|year| year.year == selected),
+        None => years.iter().max_by_key(|year| year.year)
+    };
+
+    let Some(year) = year else {
+        let registered: Vec<_> = years.iter().map(|year| year.year.to_string()).collect();
+        eprintln!("No solutions are registered for that year (available: {}.)", registered.join(", "));
+        std::process::exit(2);
+    };
+
+    let result = match options.command {
+        Command::Run => run(year, &options).map(|_| ()),
+        Command::Verify => answers::verify(year, &options),
+        Command::Record => answers::record(year, &options),
+        Command::Bench => bench::bench(year, &options),
+        Command::New => scaffold::new_day(year, &options),
+        Command::Encrypt => inputs::encrypt_inputs(year, &options),
+        Command::Submit => submit::submit(year, &options),
+        Command::Fetch => fetch::fetch_inputs(year, &options),
+        Command::Extract => extract::extract(year, &options),
+        Command::Watch => watch::watch(year, &options),
+        Command::Stats => unreachable!("Stats are handled above.")
+    };
+
+    if let Err(err) = result {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+}
+
+/// Run the days selected by `options`, printing each outcome as it completes.
+pub fn run(year: &Year, options: &Options) -> Result<Vec<Outcome>, String> {
+    let days = select_days(year, options)?;
+    let mut outcomes = Vec::with_capacity(days.len());
+    let mut reporter = Reporter::new(options.format, year.year, std::io::stdout().lock());
+
+    install_panic_hook();
+
+    for day in days {
+        let outcome = solve(year, day, options)?;
+
+        reporter
+            .report(&outcome)
+            .map_err(|err| format!("Could not write results: {err}"))?;
+
+        outcomes.push(outcome);
+    }
+
+    Ok(outcomes)
+}
+
+/// Resolve the days selected by `options` against those registered for the year.
+pub fn select_days<'y>(year: &'y Year, options: &Options) -> Result<Vec<&'y Day>, String> {
+    let days: Vec<_> = match &options.days {
+        Some(range) => year.days
+            .iter()
+            .filter(|d| range.contains(&d.day))
+            .collect(),
+        None => year.days.iter().collect()
+    };
+
+    if days.is_empty() {
+        return Err(format!("No solutions are registered for the selected days of {}.", year.year));
+    }
+
+    if !matches!(options.input, Input::Default) && days.len() > 1 {
+        return Err("An explicit input can only be used with a single day.".to_owned());
+    }
+
+    Ok(days)
+}
+
+/// Load the input(s) for and run a single day according to `options`.
+pub fn solve(year: &Year, day: &Day, options: &Options) -> Result<Outcome, String> {
+    let inputs = load_inputs(year, day, options)?;
+    Ok(solve_inputs(day, &inputs))
+}
+
+/// Load the input for each part selected by `options`, and set the parameter mode to match.
+pub fn load_inputs(year: &Year, day: &Day, options: &Options) -> Result<Vec<(Part, String)>, String> {
+    let parts = options.parts();
+    let mode = match options.example {
+        true => Mode::Example,
+        false => Mode::Real
+    };
+
+    let inputs: Vec<(Part, String)> = match &options.input {
+        Input::Default => parts
+            .iter()
+            .map(|part| {
+                // The loaders can only report problems (like a missing key) by panicking.
+                let puzzle = panic::catch_unwind(|| match mode {
+                    Mode::Example => (year.load_test)(day.day, part.flag()),
+                    Mode::Real => (year.load)(day.day)
+                });
+
+                puzzle
+                    .map(|puzzle| (*part, puzzle))
+                    .map_err(|payload| {
+                        // The message is meant for the user, so leave off the location the hook recorded.
+                        LAST_PANIC.with(|last| last.borrow_mut().take());
+                        payload_text(&*payload).unwrap_or_else(|| "Puzzle input could not be read.".to_owned())
+                    })
+            })
+            .collect::<Result<_, _>>()?,
+        Input::Path(path) => {
+            let puzzle = std::fs::read_to_string(path)
+                .map_err(|err| format!("Could not read {}: {err}", path.display()))?;
+
+            parts.iter().map(|part| (*part, puzzle.clone())).collect()
+        },
+        Input::Stdin => {
+            let mut puzzle = String::new();
+            std::io::stdin()
+                .read_to_string(&mut puzzle)
+                .map_err(|err| format!("Could not read standard input: {err}"))?;
+
+            parts.iter().map(|part| (*part, puzzle.clone())).collect()
+        }
+    };
+
+    // The loaders set the mode themselves, but explicit inputs bypass them.
+    params::set_mode(mode);
+
+    Ok(inputs)
+}
+
+/// Run a single day against already loaded inputs.
+pub fn solve_inputs(day: &Day, inputs: &[(Part, String)]) -> Outcome {
+    // Examples can differ between parts; only parse once if they don't.
+    let mut outcome = Outcome::new(day.day);
+    let mut remaining = inputs;
+
+    while let Some((_, puzzle)) = remaining.first() {
+        let shared = remaining
+            .iter()
+            .take_while(|(_, other)| other == puzzle)
+            .count();
+
+        let batch: Vec<_> = remaining[..shared].iter().map(|(part, _)| *part).collect();
+        outcome.merge((day.solve)(puzzle, &batch));
+        remaining = &remaining[shared..];
+    }
+
+    outcome
+}
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Replace the default panic hook with one that quietly records the message and
+/// location of string panics, so that they can be reported alongside the outcome.
+pub(crate) fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        if let Some(message) = panic_message(info.payload()) {
+            let location = info
+                .location()
+                .map(|l| format!(" at {}:{}", l.file(), l.line()))
+                .unwrap_or_default();
+
+            LAST_PANIC.with(|last| *last.borrow_mut() = Some(format!("{message}{location}")));
+        }
+    }));
+}
+
+fn payload_text(payload: &(dyn Any + Send)) -> Option<String> {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> Option<String> {
+    let message = payload_text(payload)?;
+
+    // Prefer the version with a location attached, if the hook recorded one.
+    let recorded = LAST_PANIC.with(|last| last.borrow_mut().take());
+
+    match recorded {
+        Some(recorded) if recorded.starts_with(&message) => Some(recorded),
+        _ => Some(message)
+    }
+}
+
+/// Pretty-prints an [`Outcome`] in the same layout as `lib_aoc`.
+pub struct Pretty<'a>(pub &'a Outcome);
+
+impl Display for Pretty<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let outcome = self.0;
+
+        writeln!(f)?;
+        writeln!(f, "--- DAY {} ---", paint(&outcome.day, "1;96"))?;
+
+        if let Some(diagnostic) = &outcome.parse_error {
+            writeln!(f, "{}: {}", paint(&"Parse error", "1;31"), diagnostic)?;
+        }
+
+        for part in &outcome.parts {
+            let color = match part.answer {
+                Answer::Solved(_) => "32",
+                _ => "31"
+            };
+
+            writeln!(f, "{}: {}", paint(&format!("Part {}", part.part), "1"), paint(&part.answer, color))?;
+        }
+
+        let target = match cfg!(debug_assertions) {
+            true => paint(&"(DEBUG)", "1;33"),
+            false => paint(&"(RELEASE)", "1;32")
+        };
+
+        writeln!(f, "\n--- BENCH {target} ---")?;
+        writeln!(f, "{}: {:?}", paint(&"Parsing", "1"), outcome.parse_time)?;
+
+        for part in &outcome.parts {
+            writeln!(f, "{}: {:?}", paint(&format!("Part {}", part.part), "1"), part.time)?;
+        }
+
+        writeln!(f, "{}: {:?}", paint(&"Total", "1"), outcome.total_time())
+    }
+}
+
+/// Wrap a value in an ANSI style, but only when writing to a terminal.
+pub(crate) fn paint(value: &impl Display, style: &str) -> String {
+    match std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none() {
+        true => format!("\x1b[{style}m{value}\x1b[0m"),
+        false => value.to_string()
+    }
+}
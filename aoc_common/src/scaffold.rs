@@ -0,0 +1,112 @@
+//! The `new` command, which scaffolds a day from the year crate's `src/day_n.rs` template.
+//!
+//! Thanks to the [`build`](crate::build) step, creating the files is all it takes to register a day.
+
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf}
+};
+
+use crate::{cli::Options, runner::Year};
+
+/// The template every day starts from, relative to the year crate.
+pub const TEMPLATE: &str = "src/day_n.rs";
+
+/// Fill in the template for `day`.
+pub fn render(template: &str, day: u8) -> String {
+    template
+        .replace("DAY_N", &format!("DAY_{day:02}"))
+        .replace("# Day N ", &format!("# Day {day} "))
+}
+
+/// Create `src/day_NN.rs` and its (empty) input files under `root`, returning the paths created.
+///
+/// Fails without touching anything if the day already exists. Input files that already exist
+/// (e.g. because they were fetched first) are left alone.
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let template_path = root.join(TEMPLATE);
+    let template = std::fs::read_to_string(&template_path)
+        .map_err(|err| format!("Could not read the template {}: {err}", template_path.display()))?;
+
+    let solution = root.join(format!("src/day_{day:02}.rs"));
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&solution)
+        .map_err(|err| match err.kind() {
+            std::io::ErrorKind::AlreadyExists => format!("Day {day} already exists ({}); refusing to overwrite it.", solution.display()),
+            _ => format!("Could not create {}: {err}", solution.display())
+        })?;
+
+    file.write_all(render(&template, day).as_bytes())
+        .map_err(|err| format!("Could not write {}: {err}", solution.display()))?;
+
+    let mut created = vec![solution];
+    let inputs = root.join("src/inputs");
+
+    std::fs::create_dir_all(&inputs)
+        .map_err(|err| format!("Could not create {}: {err}", inputs.display()))?;
+
+    for name in [format!("{day:02}.txt"), format!("test_{day:02}.txt")] {
+        let path = inputs.join(name);
+
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(_) => created.push(path),
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => (),
+            Err(err) => return Err(format!("Could not create {}: {err}", path.display()))
+        }
+    }
+
+    Ok(created)
+}
+
+/// Scaffold the single day selected by `options` in the selected year crate.
+pub fn new_day(year: &Year, options: &Options) -> Result<(), String> {
+    let day = match &options.days {
+        Some(days) if days.start() == days.end() => *days.start(),
+        _ => return Err("The new command needs exactly one day (e.g. new 7).".to_owned())
+    };
+
+    for path in scaffold(&year.path(""), day)? {
+        println!("Created {}", path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE_TEXT: &str = "//! # Day N - \n\nimpl Solution<DAY_N> for Solutions {}\n\nderive_tests!(Solutions, DAY_N);";
+
+    #[test]
+    fn rendering() {
+        assert_eq!(
+            render(TEMPLATE_TEXT, 7),
+            "//! # Day 7 - \n\nimpl Solution<DAY_07> for Solutions {}\n\nderive_tests!(Solutions, DAY_07);"
+        );
+    }
+
+    #[test]
+    fn refuses_to_overwrite() {
+        let root = std::env::temp_dir().join(format!("aoc_common_scaffold_{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src/inputs")).unwrap();
+        std::fs::write(root.join(TEMPLATE), TEMPLATE_TEXT).unwrap();
+        std::fs::write(root.join("src/inputs/07.txt"), "fetched").unwrap();
+
+        let created = scaffold(&root, 7).unwrap();
+
+        assert_eq!(created, [root.join("src/day_07.rs"), root.join("src/inputs/test_07.txt")]);
+        assert!(std::fs::read_to_string(root.join("src/day_07.rs")).unwrap().contains("DAY_07"));
+        assert_eq!(std::fs::read_to_string(root.join("src/inputs/07.txt")).unwrap(), "fetched");
+
+        std::fs::write(root.join("src/day_07.rs"), "// my solution").unwrap();
+        assert!(scaffold(&root, 7).is_err());
+        assert_eq!(std::fs::read_to_string(root.join("src/day_07.rs")).unwrap(), "// my solution");
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,309 @@
+//! Generic graph searches over a neighbor closure.
+//!
+//! None of these functions need an explicit graph structure - nodes can be any
+//! hashable value (grid positions, string IDs, whole game states...) and edges
+//! are discovered lazily by calling `neighbors` on each node as it's explored.
+//!
+//! All the searches accept multiple start nodes and stop at the first node that
+//! satisfies the `goal` predicate, returning the full [`Path`] to it.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add
+};
+
+/// A path found by one of the searches in this module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    /// Every node along the path, from the start node to the goal (inclusive.)
+    pub nodes: Vec<N>,
+    /// The total cost of the path - for [`bfs`], the number of edges traversed.
+    pub cost: C,
+}
+
+impl<N, C> Path<N, C> {
+    pub fn start(&self) -> &N {
+        &self.nodes[0]
+    }
+
+    pub fn goal(&self) -> &N {
+        &self.nodes[self.nodes.len() - 1]
+    }
+}
+
+/// Breadth-first search, where every edge costs one step.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool
+) -> Option<Path<N, usize>> where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>
+{
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !parents.contains_key(&start) {
+            parents.insert(start.clone(), None);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, depth)) = queue.pop_front() {
+        if goal(&node) {
+            return Some(Path {
+                nodes: reconstruct(&parents, node),
+                cost: depth,
+            })
+        }
+
+        for next in neighbors(&node) {
+            if !parents.contains_key(&next) {
+                parents.insert(next.clone(), Some(node.clone()));
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Exhaustive breadth-first search, yielding the step distance to every node
+/// reachable from the starts (themselves included, at distance zero.)
+pub fn bfs_reach<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I
+) -> HashMap<N, usize> where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, depth)) = queue.pop_front() {
+        for next in neighbors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), depth + 1);
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Dijkstra's algorithm. `neighbors` yields `(node, edge cost)` pairs; costs must
+/// be non-negative.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool
+) -> Option<Path<N, C>> where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>
+{
+    astar(starts, neighbors, |_| C::default(), goal)
+}
+
+/// A* search. `neighbors` yields `(node, edge cost)` pairs; costs must be non-negative.
+///
+/// `heuristic` estimates the remaining cost from a node to the nearest goal. It must never
+/// *overestimate* that cost, or the returned path may not be the cheapest one.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool
+) -> Option<Path<N, C>> where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>
+{
+    // Nodes are interned into a Vec so that the heap only has to order
+    // plain indices, rather than requiring N: Ord.
+    let mut nodes: Vec<N> = Vec::new();
+    let mut costs: Vec<C> = Vec::new();
+    let mut parents: Vec<Option<usize>> = Vec::new();
+    let mut indices: HashMap<N, usize> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if !indices.contains_key(&start) {
+            let index = nodes.len();
+            heap.push(Reverse((heuristic(&start), C::default(), index)));
+            indices.insert(start.clone(), index);
+            nodes.push(start);
+            costs.push(C::default());
+            parents.push(None);
+        }
+    }
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        // Skip stale heap entries that have since been superseded by a cheaper path.
+        if cost > costs[index] {
+            continue;
+        }
+
+        if goal(&nodes[index]) {
+            let mut path = vec![index];
+
+            while let Some(parent) = parents[path[path.len() - 1]] {
+                path.push(parent);
+            }
+
+            return Some(Path {
+                nodes: path
+                    .into_iter()
+                    .rev()
+                    .map(|i| nodes[i].clone())
+                    .collect(),
+                cost
+            })
+        }
+
+        for (next, weight) in neighbors(&nodes[index]) {
+            let next_cost = cost + weight;
+
+            let next_index = match indices.get(&next) {
+                Some(&i) if next_cost >= costs[i] => continue,
+                Some(&i) => {
+                    costs[i] = next_cost;
+                    parents[i] = Some(index);
+                    i
+                },
+                None => {
+                    let i = nodes.len();
+                    indices.insert(next.clone(), i);
+                    nodes.push(next);
+                    costs.push(next_cost);
+                    parents.push(Some(index));
+                    i
+                }
+            };
+
+            let estimate = next_cost + heuristic(&nodes[next_index]);
+            heap.push(Reverse((estimate, next_cost, next_index)));
+        }
+    }
+
+    None
+}
+
+fn reconstruct<N>(parents: &HashMap<N, Option<N>>, goal: N) -> Vec<N> where
+    N: Clone + Eq + Hash
+{
+    let mut path = vec![goal];
+
+    while let Some(Some(parent)) = parents.get(&path[path.len() - 1]) {
+        path.push(parent.clone());
+    }
+
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Grid, Point};
+
+    const MAZE: &str = "\
+        S..#....\n\
+        .#.#.##.\n\
+        .#...#..\n\
+        .####.#.\n\
+        ......#E";
+
+    #[test]
+    fn grid_paths() {
+        let maze = Grid::parse(MAZE, |c| c);
+        let start = maze.position(|c| *c == 'S').unwrap();
+        let open = |pos: &(usize, usize)| {
+            maze.neighbors(*pos)
+                .filter(|next| maze[*next] != '#')
+                .collect::<Vec<_>>()
+        };
+
+        let path = bfs([start], open, |pos| maze[*pos] == 'E').unwrap();
+
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.nodes.len(), 16);
+        assert_eq!(path.start(), &start);
+        assert_eq!(maze[*path.goal()], 'E');
+        assert!(path.nodes.windows(2).all(|w| open(&w[0]).contains(&w[1])));
+
+        let weighted = |pos: &(usize, usize)| {
+            open(pos)
+                .into_iter()
+                .map(|next| (next, 1))
+                .collect::<Vec<_>>()
+        };
+
+        let dijkstra = dijkstra([start], weighted, |pos| maze[*pos] == 'E').unwrap();
+        let astar = astar(
+            [start],
+            weighted,
+            |&(r, c)| (4 - r) + (7 - c),
+            |pos| maze[*pos] == 'E'
+        ).unwrap();
+
+        assert_eq!(dijkstra.cost, path.cost);
+        assert_eq!(astar.cost, path.cost);
+    }
+
+    #[test]
+    fn multi_source() {
+        let maze = Grid::parse(MAZE, |c| c);
+        let open = |pos: &(usize, usize)| {
+            maze.neighbors(*pos)
+                .filter(|next| maze[*next] != '#')
+                .collect::<Vec<_>>()
+        };
+
+        let path = bfs([(0, 0), (0, 7)], open, |pos| maze[*pos] == 'E').unwrap();
+
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.start(), &(0, 7));
+        assert_eq!(bfs_reach([(0, 0)], open).len(), 27);
+    }
+
+    #[test]
+    fn weighted_edges() {
+        // The direct edge is more expensive than the detour.
+        let edges = |node: &char| match node {
+            'a' => vec![('b', 1), ('d', 10)],
+            'b' => vec![('c', 2)],
+            'c' => vec![('d', 3)],
+            _ => vec![]
+        };
+
+        let path = dijkstra(['a'], edges, |n| *n == 'd').unwrap();
+
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.nodes, ['a', 'b', 'c', 'd']);
+        assert_eq!(dijkstra(['d'], edges, |n| *n == 'a'), None);
+    }
+
+    #[test]
+    fn unbounded_nodes() {
+        let target = Point::new(3, -4);
+        let path = bfs(
+            [Point::ORIGIN],
+            |p: &Point| p.neighbors(),
+            |p| *p == target
+        ).unwrap();
+
+        assert_eq!(path.cost as i64, target.manhattan_dist(&Point::ORIGIN));
+    }
+}
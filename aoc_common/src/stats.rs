@@ -0,0 +1,320 @@
+//! Charts of solve times and ranks, and the README's completion table, built from each day's [metadata](crate::meta).
+//!
+//! `stats` covers every registered year unless one is selected with `--year`. With `--readme`,
+//! it also regenerates the table at the top of the workspace's `README.md` (for all years), counting
+//! a star for every part of a registered day with a verified answer in the year's [answers file](crate::answers).
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    answers::Answers,
+    cli::Options,
+    meta::DayMeta,
+    runner::{Part, Year}
+};
+
+const BAR_WIDTH: usize = 40;
+const TITLE_WIDTH: usize = 24;
+
+/// The metadata of the registered days of `year` selected by `options`.
+pub fn metas(year: &Year, options: &Options) -> Result<Vec<DayMeta>, String> {
+    year.days
+        .iter()
+        .filter(|day| options.days.as_ref().is_none_or(|days| days.contains(&day.day)))
+        .map(|day| year.meta(day.day))
+        .collect()
+}
+
+/// A bar of `first` solid cells followed by `total - first` shaded ones.
+fn bar(first: usize, total: usize) -> String {
+    let first = first.min(total);
+    format!("{}{}", "█".repeat(first), "░".repeat(total - first))
+}
+
+/// How many cells of a bar `BAR_WIDTH` long `value` takes up, out of `max`.
+fn cells(value: f64, max: f64) -> usize {
+    match max > 0.0 {
+        true => ((value / max) * BAR_WIDTH as f64).round() as usize,
+        false => 0
+    }
+}
+
+fn label(meta: &DayMeta) -> String {
+    let title: String = meta.title.as_deref().unwrap_or("").chars().take(TITLE_WIDTH).collect();
+    format!("{:>2} {title:<TITLE_WIDTH$}", meta.day)
+}
+
+fn column<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "-".to_owned(), |value| value.to_string())
+}
+
+/// Render the solve time and rank charts for one year.
+pub fn charts(year: u16, metas: &[DayMeta]) -> String {
+    let noted: Vec<_> = metas
+        .iter()
+        .filter(|meta| meta.parts.iter().any(Option::is_some))
+        .collect();
+
+    if noted.is_empty() {
+        return format!("{year}: no completion times noted down in the day modules.\n");
+    }
+
+    let mut out = format!("{year}: time to solve (█ part one, ░ part two; capped at a day)\n");
+
+    let secs = |meta: &DayMeta, part| meta.part(part).map(|c| c.time.capped().as_secs_f64());
+    let last = |meta: &DayMeta| secs(meta, Part::Two).or(secs(meta, Part::One)).unwrap_or(0.0);
+    let max = noted.iter().map(|meta| last(meta)).fold(0.0, f64::max);
+
+    for meta in &noted {
+        let first = secs(meta, Part::One).unwrap_or(0.0);
+
+        out += &format!(
+            "{} {:<w$}  {:>8}  {:>8}\n",
+            label(meta),
+            bar(cells(first, max), cells(last(meta), max)),
+            column(meta.part(Part::One).map(|c| c.time)),
+            column(meta.part(Part::Two).map(|c| c.time)),
+            w = BAR_WIDTH
+        );
+    }
+
+    out += &format!("\n{year}: global rank (of the last part solved)\n");
+
+    let rank = |meta: &DayMeta, part| meta.part(part).and_then(|c| c.rank);
+    let last = |meta: &DayMeta| rank(meta, Part::Two).or(rank(meta, Part::One));
+    let max = noted.iter().filter_map(|meta| last(meta)).max().unwrap_or(0);
+
+    for meta in &noted {
+        let width = cells(last(meta).unwrap_or(0) as f64, max as f64);
+
+        out += &format!(
+            "{} {:<w$}  {:>8}  {:>8}\n",
+            label(meta),
+            bar(width, width),
+            column(rank(meta, Part::One)),
+            column(rank(meta, Part::Two)),
+            w = BAR_WIDTH
+        );
+    }
+
+    let complete = metas.iter().filter(|meta| meta.complete()).count();
+    out += &format!("\nBoth parts noted as completed for {complete} day(s).\n");
+
+    out
+}
+
+/// Every part of every day.
+pub const MAX_STARS: usize = 50;
+
+/// One row of the README table.
+pub struct Row {
+    pub year: u16,
+    pub stars: usize,
+}
+
+/// The number of parts of the year's registered days with a verified answer.
+pub fn stars(year: &Year) -> Result<usize, String> {
+    let answers = Answers::load(year.path(year.answers))?;
+
+    let stars = year.days
+        .iter()
+        .flat_map(|day| Part::ALL.map(|part| answers.get(day.day, part)))
+        .filter(Option::is_some)
+        .count();
+
+    Ok(stars)
+}
+
+/// The width of `text` in a monospaced font, counting emoji (and the star) as two columns.
+fn display_width(text: &str) -> usize {
+    text.chars()
+        .map(|c| match c {
+            '⭐' | '\u{1f000}'.. => 2,
+            _ => 1
+        })
+        .sum()
+}
+
+/// Render the README's completion table.
+pub fn table(rows: &[Row]) -> String {
+    let header = ["Year", "Language", "Dependencies", "Complete"];
+
+    let cells: Vec<[String; 4]> = rows
+        .iter()
+        .map(|row| {
+            let complete = match row.stars >= MAX_STARS {
+                true => "Yes",
+                false => "No"
+            };
+
+            let complete = format!("{complete} ({}/{MAX_STARS} ⭐)", row.stars);
+
+            [row.year.to_string(), "Rust 🦀".to_owned(), "`std` only[^1]".to_owned(), complete]
+        })
+        .collect();
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            cells
+                .iter()
+                .map(|row| display_width(&row[i]))
+                .chain([header[i].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let line = |row: &[&str]| {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!(" {cell}{} ", " ".repeat(width - display_width(cell))))
+            .collect();
+
+        format!("|{}|\n", cells.join("|"))
+    };
+
+    let mut out = line(&header);
+    out += &format!("|{}|\n", widths.iter().map(|width| "-".repeat(width + 2)).collect::<Vec<_>>().join("|"));
+
+    for row in &cells {
+        out += &line(&row.each_ref().map(String::as_str));
+    }
+
+    out
+}
+
+/// Replace the first table (a run of lines starting with `|`) in `readme` with `table`.
+pub fn replace_table(readme: &str, table: &str) -> Result<String, String> {
+    let lines: Vec<&str> = readme.lines().collect();
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("| Year"))
+        .ok_or("The README has no table starting with a '| Year' header.")?;
+
+    let end = lines[start..]
+        .iter()
+        .position(|line| !line.starts_with('|'))
+        .map_or(lines.len(), |offset| start + offset);
+
+    let mut out = lines[..start].join("\n");
+
+    if start > 0 {
+        out += "\n";
+    }
+
+    out += table;
+    out += &lines[end..].iter().map(|line| format!("{line}\n")).collect::<String>();
+
+    if !readme.ends_with('\n') {
+        out.pop();
+    }
+
+    Ok(out)
+}
+
+/// The workspace's README, next to the year crates.
+fn readme_path(year: &Year) -> PathBuf {
+    let root = Path::new(year.root);
+    root.parent().unwrap_or(root).join("README.md")
+}
+
+/// Print the charts for the selected years, and regenerate the README table if asked to.
+pub fn stats(years: &[&Year], options: &Options) -> Result<(), String> {
+    let mut years = years.to_vec();
+    years.sort_by_key(|year| year.year);
+
+    let selected: Vec<_> = years
+        .iter()
+        .filter(|year| options.year.is_none_or(|selected| year.year == selected))
+        .collect();
+
+    if selected.is_empty() {
+        let registered: Vec<_> = years.iter().map(|year| year.year.to_string()).collect();
+        return Err(format!("No solutions are registered for that year (available: {}.)", registered.join(", ")));
+    }
+
+    for (i, year) in selected.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        print!("{}", charts(year.year, &metas(year, options)?));
+    }
+
+    if !options.readme {
+        return Ok(());
+    }
+
+    let (path, readme, updated) = readme(&years)?;
+    let display = years[0].path(&path);
+
+    if updated == readme {
+        println!("\n{} is up to date.", display.display());
+        return Ok(());
+    }
+
+    std::fs::write(&path, updated).map_err(|err| format!("Could not write {}: {err}", display.display()))?;
+    println!("\nUpdated the table in {}.", display.display());
+
+    Ok(())
+}
+
+/// The path of the workspace's README, its current contents, and its contents with an up to date table.
+pub fn readme(years: &[&Year]) -> Result<(PathBuf, String, String), String> {
+    let mut rows = years
+        .iter()
+        .map(|year| stars(year).map(|stars| Row { year: year.year, stars }))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    rows.sort_by_key(|row| row.year);
+
+    let path = readme_path(years.first().ok_or("No years are registered.")?);
+
+    let readme = std::fs::read_to_string(&path)
+        .map_err(|err| format!("Could not read {}: {err}", path.display()))?;
+
+    let updated = replace_table(&readme, &table(&rows)).map_err(|err| format!("{}: {err}", path.display()))?;
+
+    Ok((path, readme, updated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn charting() {
+        let source = "//! # Day 4 - Camp Cleanup\n//! Puzzle opened on time.\n\
+                      //! - P1 completed @ 00:10:00 (500)\n//! - P2 completed @ 00:20:00 (1000)\n";
+        let metas = [DayMeta::parse(4, source), DayMeta::parse(5, "use super::*;\n")];
+        let charts = charts(2022, &metas);
+
+        assert!(charts.contains(&format!(" 4 Camp Cleanup             {}{}  00:10:00  00:20:00\n", "█".repeat(20), "░".repeat(20))));
+        assert!(charts.contains(&format!("{}       500      1000\n", "█".repeat(40))));
+        assert!(!charts.contains(" 5 "));
+        assert!(charts.ends_with("for 1 day(s).\n"));
+
+        assert_eq!(super::charts(2024, &metas[1..]), "2024: no completion times noted down in the day modules.\n");
+    }
+
+    #[test]
+    fn readme_table() {
+        let table = table(&[Row { year: 2022, stars: 34 }, Row { year: 2024, stars: 50 }]);
+
+        assert_eq!(table, "\
+| Year | Language | Dependencies   | Complete       |
+|------|----------|----------------|----------------|
+| 2022 | Rust 🦀  | `std` only[^1] | No (34/50 ⭐)  |
+| 2024 | Rust 🦀  | `std` only[^1] | Yes (50/50 ⭐) |
+");
+
+        let readme = "# Title\nIntro.\n\n| Year | Old |\n|---|---|\n| 2022 | No |\n\n## Next\n";
+        let updated = replace_table(readme, &table).unwrap();
+
+        assert_eq!(updated, format!("# Title\nIntro.\n\n{table}\n## Next\n"));
+        assert_eq!(replace_table(&updated, &table).unwrap(), updated);
+        assert!(replace_table("# Title\n", &table).is_err());
+    }
+}
@@ -0,0 +1,476 @@
+//! Submitting answers to the Advent of Code website.
+//!
+//! The `submit` command runs a single part against the real input and posts its answer,
+//! with the same session cookie and [`Http`] backends as the [fetcher](crate::fetch).
+//! The site's reply is boiled down to a [`Verdict`].
+//!
+//! Every attempt is appended to a local log (`attempts.log` in the year crate, which isn't
+//! committed), and checked against it first: answers already known to be wrong are never
+//! sent again, and neither are numbers on the wrong side of an earlier "too high" or
+//! "too low". Correct answers are also stored in the year's [answers file](crate::answers).
+
+use std::{
+    fmt::Display,
+    io::Write,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH}
+};
+
+use crate::{
+    answers::{self, Answers},
+    cli::{Input, Options},
+    fetch::{self, Curl, Http},
+    runner::{self, paint, Answer, Part, Year}
+};
+
+/// Where attempts are logged, relative to the year crate.
+pub const ATTEMPTS: &str = "attempts.log";
+
+/// The outcome of submitting an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted too soon after the last attempt; includes how long is left to wait, if known.
+    RateLimited(Option<Duration>),
+    /// The part is either locked or already solved.
+    WrongLevel,
+    /// A reply that couldn't be made sense of, with its text.
+    Unknown(String)
+}
+
+impl Verdict {
+    /// Interpret the HTML the site replies with.
+    pub fn parse(html: &str) -> Self {
+        let text = article_text(html);
+
+        if text.contains("That's the right answer") {
+            Verdict::Correct
+        }
+        else if text.contains("You gave an answer too recently") {
+            Verdict::RateLimited(wait_time(&text))
+        }
+        else if text.contains("That's not the right answer") {
+            match (text.contains("too high"), text.contains("too low")) {
+                (true, _) => Verdict::TooHigh,
+                (_, true) => Verdict::TooLow,
+                _ => Verdict::Wrong
+            }
+        }
+        else if text.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        }
+        else {
+            Verdict::Unknown(text.chars().take(200).collect())
+        }
+    }
+
+    /// Whether the attempt showed the answer to be wrong.
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+
+    /// The token used for this verdict in the attempt log.
+    fn token(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::RateLimited(_) => "rate-limited",
+            Verdict::WrongLevel => "wrong-level",
+            Verdict::Unknown(_) => "unknown"
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Self> {
+        Some(match token {
+            "correct" => Verdict::Correct,
+            "wrong" => Verdict::Wrong,
+            "too-high" => Verdict::TooHigh,
+            "too-low" => Verdict::TooLow,
+            "rate-limited" => Verdict::RateLimited(None),
+            "wrong-level" => Verdict::WrongLevel,
+            "unknown" => Verdict::Unknown(String::new()),
+            _ => return None
+        })
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::Wrong => write!(f, "That's not the right answer."),
+            Verdict::TooHigh => write!(f, "That's not the right answer; it's too high."),
+            Verdict::TooLow => write!(f, "That's not the right answer; it's too low."),
+            Verdict::RateLimited(Some(wait)) => write!(f, "Answered too recently; wait {}s before trying again.", wait.as_secs()),
+            Verdict::RateLimited(None) => write!(f, "Answered too recently; wait a bit before trying again."),
+            Verdict::WrongLevel => write!(f, "That part is either locked or already solved."),
+            Verdict::Unknown(text) => write!(f, "Unrecognized reply: {text}")
+        }
+    }
+}
+
+/// The text of the page's `<article>` (or the whole page, if there isn't one), without tags.
+fn article_text(html: &str) -> String {
+    let article = html
+        .find("<article")
+        .and_then(|start| {
+            let end = html[start..].find("</article>")?;
+            Some(&html[start..start + end])
+        })
+        .unwrap_or(html);
+
+    let mut text = String::with_capacity(article.len());
+    let mut in_tag = false;
+
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => ()
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse the "You have 1m 5s left to wait" part of a rate limiting reply.
+fn wait_time(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+
+    text[start..end]
+        .split_whitespace()
+        .map(|token| {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value = value.parse::<u64>().ok()?;
+
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Percent-encode a form value.
+fn url_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}"))
+        }
+    }
+
+    encoded
+}
+
+/// One logged submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub day: u8,
+    pub part: Part,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+impl Display for Attempt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:02}.{} {}: {}", self.time, self.day, self.part, self.verdict.token(), answers::escape(&self.answer))
+    }
+}
+
+/// Every answer submitted for a year, with lines of the form `TIME DD.P VERDICT: ANSWER`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AttemptLog {
+    pub attempts: Vec<Attempt>,
+}
+
+impl AttemptLog {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut attempts = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let malformed = || format!("Malformed attempt on line {}: '{line}'", i + 1);
+
+            let (key, answer) = line.split_once(": ").ok_or_else(malformed)?;
+            let mut fields = key.split(' ');
+
+            let (Some(time), Some(day_part), Some(verdict), None) = (fields.next(), fields.next(), fields.next(), fields.next()) else {
+                return Err(malformed());
+            };
+
+            let (day, part) = day_part.split_once('.').ok_or_else(malformed)?;
+
+            attempts.push(Attempt {
+                time: time.parse().map_err(|_| malformed())?,
+                day: day.parse().map_err(|_| malformed())?,
+                part: match part {
+                    "1" => Part::One,
+                    "2" => Part::Two,
+                    _ => return Err(malformed())
+                },
+                verdict: Verdict::from_token(verdict).ok_or_else(malformed)?,
+                answer: answers::unescape(answer),
+            });
+        }
+
+        Ok(Self { attempts })
+    }
+
+    /// Load a log, treating a missing file as empty.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|err| format!("{}: {err}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(format!("Could not read {}: {err}", path.display()))
+        }
+    }
+
+    /// Record an attempt, appending it to the log file at `path`.
+    pub fn append(&mut self, path: impl AsRef<Path>, attempt: Attempt) -> Result<(), String> {
+        let path = path.as_ref();
+
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{attempt}"))
+            .map_err(|err| format!("Could not write {}: {err}", path.display()))?;
+
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    fn for_part(&self, day: u8, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |a| a.day == day && a.part == part)
+    }
+
+    /// The accepted answer to a part, if it has one.
+    pub fn correct(&self, day: u8, part: Part) -> Option<&str> {
+        self.for_part(day, part)
+            .find(|a| a.verdict == Verdict::Correct)
+            .map(|a| a.answer.as_str())
+    }
+
+    /// Check whether `answer` is worth submitting, given the earlier attempts.
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Result<(), String> {
+        if let Some(correct) = self.correct(day, part) {
+            return Err(format!("Day {day} part {part} is already solved (the answer was {correct})."));
+        }
+
+        let numeric = answer.trim().parse::<i128>().ok();
+
+        for attempt in self.for_part(day, part).filter(|a| a.verdict.is_wrong()) {
+            if attempt.answer == answer {
+                return Err(format!("{answer} was already submitted for day {day} part {part}: {}", attempt.verdict));
+            }
+
+            let (Some(answer), Some(earlier)) = (numeric, attempt.answer.trim().parse::<i128>().ok()) else {
+                continue
+            };
+
+            match attempt.verdict {
+                Verdict::TooHigh if answer >= earlier => return Err(format!("{answer} can't be right, as {earlier} was already too high.")),
+                Verdict::TooLow if answer <= earlier => return Err(format!("{answer} can't be right, as {earlier} was already too low.")),
+                _ => ()
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Posts answers to the website.
+pub struct Submitter<H> {
+    pub http: H,
+    pub base_url: String,
+    pub session: String,
+}
+
+impl Submitter<Curl> {
+    /// A submitter for the real website, using the session cookie for the year crate at `root`.
+    pub fn new(root: &Path) -> Result<Self, String> {
+        Ok(Self {
+            http: Curl,
+            base_url: fetch::BASE_URL.to_owned(),
+            session: fetch::session(root)?,
+        })
+    }
+}
+
+impl<H: Http> Submitter<H> {
+    pub fn submit(&self, year: u16, day: u8, part: Part, answer: &str) -> Result<Verdict, String> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let form = format!("level={part}&answer={}", url_encode(answer));
+
+        let response = self.http
+            .post(&url, &self.session, &form)
+            .map_err(|err| format!("Could not submit the answer: {err}."))?;
+
+        match response.status {
+            200 => Ok(Verdict::parse(&response.body)),
+            302 | 400 | 500 => Err("The session cookie was rejected; it has probably expired.".to_owned()),
+            status => Err(format!("Submitting to {url} failed with HTTP status {status}."))
+        }
+    }
+}
+
+/// Run the selected part of a single day, and submit its answer unless the log rules it out.
+pub fn submit(year: &Year, options: &Options) -> Result<(), String> {
+    if options.example || options.input != Input::Default {
+        return Err("Only answers for the real input can be submitted; submit can't be combined with --example or --input.".to_owned());
+    }
+
+    let day = match &options.days {
+        Some(days) if days.start() == days.end() => *days.start(),
+        _ => return Err("The submit command needs exactly one day (e.g. submit 7).".to_owned())
+    };
+
+    let log_path = year.path(ATTEMPTS);
+    let answers_path = year.path(year.answers);
+    let mut log = AttemptLog::load(&log_path)?;
+    let mut answers = Answers::load(&answers_path)?;
+
+    // Default to the first part that hasn't been solved yet.
+    let part = match options.part {
+        Some(part) => part,
+        None => Part::ALL
+            .into_iter()
+            .find(|part| log.correct(day, *part).is_none() && answers.get(day, *part).is_none())
+            .ok_or_else(|| format!("Both parts of day {day} are already solved."))?
+    };
+
+    let registered = year
+        .day(day)
+        .ok_or_else(|| format!("No solution is registered for day {day} of {}.", year.year))?;
+
+    let submitter = Submitter::new(Path::new(year.root))?;
+
+    runner::install_panic_hook();
+
+    let options = Options { part: Some(part), ..options.clone() };
+    let outcome = runner::solve(year, registered, &options)?;
+
+    let answer = match outcome.parts.first().map(|p| &p.answer) {
+        Some(Answer::Solved(answer)) => answer.clone(),
+        Some(other) => return Err(format!("Day {day} part {part} has no answer to submit ({other}).")),
+        None => return Err(format!("Day {day} part {part} has no answer to submit."))
+    };
+
+    log.check(day, part, &answer)?;
+
+    println!("Submitting {} for day {day} part {part} of {}...", answers::escape(&answer), year.year);
+
+    let verdict = submitter.submit(year.year, day, part, &answer)?;
+
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+
+    log.append(&log_path, Attempt { time, day, part, verdict: verdict.clone(), answer: answer.clone() })?;
+
+    match verdict {
+        Verdict::Correct => {
+            println!("{}", paint(&verdict, "1;32"));
+
+            if answers.get(day, part).is_none() {
+                answers.insert(day, part, answer);
+                answers.save(&answers_path)?;
+                println!("Recorded the answer in {}.", answers_path.display());
+            }
+
+            Ok(())
+        },
+        verdict => Err(paint(&verdict, "1;31"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::{stub, PlainHttp};
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main><article><p>{article}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn verdicts() {
+        let cases = [
+            ("That's the right answer! You are <em>one gold star</em> closer to saving Christmas.", Verdict::Correct),
+            ("That's not the right answer; your answer is too high. If you're stuck...", Verdict::TooHigh),
+            ("That's not the right answer; your answer is too low.", Verdict::TooLow),
+            ("That's not the right answer. If you're stuck, make sure you're using the full input data.", Verdict::Wrong),
+            ("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.", Verdict::RateLimited(Some(Duration::from_secs(65)))),
+            ("You don't seem to be solving the right level.  Did you already complete it?", Verdict::WrongLevel),
+        ];
+
+        for (article, verdict) in cases {
+            assert_eq!(Verdict::parse(&page(article)), verdict);
+        }
+
+        assert!(matches!(Verdict::parse("<p>Something else</p>"), Verdict::Unknown(text) if text == "Something else"));
+    }
+
+    #[test]
+    fn log() {
+        let text = "\
+1733400000 05.1 too-high: 500
+1733400100 05.1 too-low: 100
+1733400200 05.1 wrong: 250
+1733400300 05.1 rate-limited: 300
+1733400400 06.1 correct: abc
+";
+        let log = AttemptLog::parse(text).unwrap();
+
+        assert_eq!(log.attempts.len(), 5);
+        assert_eq!(log.attempts[0].to_string(), "1733400000 05.1 too-high: 500");
+
+        assert!(log.check(5, Part::One, "250").is_err());
+        assert!(log.check(5, Part::One, "600").is_err());
+        assert!(log.check(5, Part::One, "100").is_err());
+        assert!(log.check(5, Part::One, "300").is_ok());
+        assert!(log.check(5, Part::Two, "250").is_ok());
+        assert!(log.check(6, Part::One, "xyz").is_err());
+        assert_eq!(log.correct(6, Part::One), Some("abc"));
+
+        assert!(AttemptLog::parse("1733400000 05.1 maybe: 5").is_err());
+        assert!(AttemptLog::parse("05.1 wrong: 5").is_err());
+    }
+
+    #[test]
+    fn submission() {
+        let (url, requests) = stub::server(vec![
+            "HTTP/1.1 200 OK\r\n\r\n<article><p>That's not the right answer; your answer is too low.</p></article>",
+        ]);
+
+        let submitter = Submitter { http: PlainHttp, base_url: url, session: "cookie".to_owned() };
+
+        assert_eq!(submitter.submit(2022, 10, Part::Two, "EHZ FZ#"), Ok(Verdict::TooLow));
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/10/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("\n\nlevel=2&answer=EHZ%20FZ%23"));
+    }
+}
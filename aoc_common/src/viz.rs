@@ -0,0 +1,303 @@
+//! Recording the states of a simulation as image frames, to inspect it without adding print statements.
+//!
+//! Only built with the `viz` feature (which each year crate forwards), so days record their
+//! frames behind `#[cfg(feature = "viz")]`:
+//! ``` ignore
+//! #[cfg(feature = "viz")]
+//! let mut recorder = aoc_common::viz::Recorder::for_day(2024, 6).every(10);
+//!
+//! // ... then, for each step of the simulation:
+//! #[cfg(feature = "viz")]
+//! recorder.grid(format_args!("step {step}"), &grid, |cell| match cell { .. });
+//! ```
+//! A recorder writes numbered frames (`frame_00000.ppm`, ...) into its directory, along with an
+//! `index.txt` listing each frame's file and label. For a day, the directory is `<year>/day_NN`
+//! under `AOC_VIZ_DIR` if it's set, and otherwise under `viz` in the working directory. Examples
+//! (which are also what the tests run) go to `<year>/day_NN_example` under `aoc_viz` in the
+//! temporary directory instead, so that test runs don't leave frames in the source tree. Frames
+//! are written as PPM, or as SVG if `AOC_VIZ_FORMAT=svg`. Any previous frames in the directory are removed first.
+//!
+//! Problems writing frames are reported once on standard error, after which the recorder does
+//! nothing; a visualization should never stop a solution from running.
+
+use std::{
+    fmt::{Display, Write as _},
+    io::Write,
+    path::{Path, PathBuf}
+};
+
+use crate::{
+    params::{self, Mode},
+    Grid
+};
+
+pub const DIR_VAR: &str = "AOC_VIZ_DIR";
+pub const FORMAT_VAR: &str = "AOC_VIZ_FORMAT";
+
+const INDEX: &str = "index.txt";
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+pub const GREY: Rgb = [128, 128, 128];
+pub const RED: Rgb = [220, 50, 47];
+pub const GREEN: Rgb = [133, 153, 0];
+pub const YELLOW: Rgb = [255, 215, 0];
+
+/// The image format of the frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FrameFormat {
+    /// Binary PPM, which almost any image viewer can open.
+    #[default]
+    Ppm,
+    Svg
+}
+
+impl FrameFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            FrameFormat::Ppm => "ppm",
+            FrameFormat::Svg => "svg"
+        }
+    }
+}
+
+impl std::str::FromStr for FrameFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ppm" => Ok(FrameFormat::Ppm),
+            "svg" => Ok(FrameFormat::Svg),
+            _ => Err(format!("'{s}' is not a valid frame format (expected ppm or svg.)"))
+        }
+    }
+}
+
+/// Writes frames into a directory.
+#[derive(Debug)]
+pub struct Recorder {
+    /// `None` once the recorder has been disabled by an error.
+    dir: Option<PathBuf>,
+    format: FrameFormat,
+    /// The side of each cell, in pixels.
+    scale: usize,
+    /// Only every `every`th frame pushed is written.
+    every: usize,
+    pushed: usize,
+    written: usize,
+}
+
+impl Recorder {
+    /// Create a recorder writing into `dir`, clearing out any frames from a previous recording.
+    pub fn new(dir: impl Into<PathBuf>, format: FrameFormat) -> std::io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+            if name == INDEX || (name.starts_with("frame_") && (name.ends_with(".ppm") || name.ends_with(".svg"))) {
+                std::fs::remove_file(&path)?;
+            }
+        }
+
+        std::fs::write(dir.join(INDEX), "")?;
+
+        Ok(Self {
+            dir: Some(dir),
+            format,
+            scale: 8,
+            every: 1,
+            pushed: 0,
+            written: 0,
+        })
+    }
+
+    /// Create a recorder for a day, configured from the environment (see the [module docs](self).)
+    pub fn for_day(year: u16, day: u8) -> Self {
+        let (default, name) = match params::mode() {
+            Mode::Real => (PathBuf::from("viz"), format!("day_{day:02}")),
+            Mode::Example => (std::env::temp_dir().join("aoc_viz"), format!("day_{day:02}_example"))
+        };
+
+        let base = std::env::var_os(DIR_VAR).map_or(default, PathBuf::from);
+
+        let format = std::env::var(FORMAT_VAR)
+            .ok()
+            .map(|format| format.parse::<FrameFormat>())
+            .transpose();
+
+        let dir = base.join(year.to_string()).join(name);
+
+        let recorder = format.and_then(|format| {
+            Self::new(&dir, format.unwrap_or_default())
+                .map_err(|err| format!("Could not prepare {}: {err}", dir.display()))
+        });
+
+        recorder.unwrap_or_else(|err| {
+            eprintln!("Not recording frames: {err}");
+            Self { dir: None, format: FrameFormat::Ppm, scale: 8, every: 1, pushed: 0, written: 0 }
+        })
+    }
+
+    /// Draw each cell as a `scale` by `scale` square.
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Only write every `n`th frame (plus the first), for long simulations.
+    pub fn every(mut self, n: usize) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    /// The directory frames are written to, unless the recorder has been disabled.
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// The number of frames written so far.
+    pub fn written(&self) -> usize {
+        self.written
+    }
+
+    /// Push a frame of `rows` by `cols` cells, coloured by `pixel(row, col)`.
+    pub fn frame(&mut self, label: impl Display, rows: usize, cols: usize, pixel: impl Fn(usize, usize) -> Rgb) {
+        let pushed = self.pushed;
+        self.pushed += 1;
+
+        if !pushed.is_multiple_of(self.every) {
+            return;
+        }
+
+        let Some(dir) = &self.dir else {
+            return
+        };
+
+        let name = format!("frame_{:05}.{}", self.written, self.format.extension());
+
+        let image = match self.format {
+            FrameFormat::Ppm => ppm(rows, cols, self.scale, &pixel),
+            FrameFormat::Svg => svg(rows, cols, self.scale, &pixel).into_bytes()
+        };
+
+        let result = std::fs::write(dir.join(&name), image).and_then(|_| {
+            std::fs::OpenOptions::new()
+                .append(true)
+                .open(dir.join(INDEX))
+                .and_then(|mut index| writeln!(index, "{name}\t{label}"))
+        });
+
+        match result {
+            Ok(_) => self.written += 1,
+            Err(err) => {
+                eprintln!("Stopped recording frames into {}: {err}", dir.display());
+                self.dir = None;
+            }
+        }
+    }
+
+    /// Push a frame of a grid, colouring each cell with `color`.
+    pub fn grid<T>(&mut self, label: impl Display, grid: &Grid<T>, color: impl Fn(&T) -> Rgb) {
+        let (rows, cols) = grid.dimensions();
+        self.frame(label, rows, cols, |row, col| color(&grid[(row, col)]));
+    }
+}
+
+/// Render a binary (P6) PPM image.
+fn ppm(rows: usize, cols: usize, scale: usize, pixel: impl Fn(usize, usize) -> Rgb) -> Vec<u8> {
+    let mut image = format!("P6\n{} {}\n255\n", cols * scale, rows * scale).into_bytes();
+    image.reserve(rows * cols * scale * scale * 3);
+
+    for row in 0..rows {
+        let line: Vec<u8> = (0..cols)
+            .flat_map(|col| {
+                let rgb = pixel(row, col);
+                std::iter::repeat_n(rgb, scale).flatten()
+            })
+            .collect();
+
+        for _ in 0..scale {
+            image.extend_from_slice(&line);
+        }
+    }
+
+    image
+}
+
+/// Render an SVG image, merging runs of same-coloured cells in each row into one rectangle.
+fn svg(rows: usize, cols: usize, scale: usize, pixel: impl Fn(usize, usize) -> Rgb) -> String {
+    let mut image = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {cols} {rows}\" shape-rendering=\"crispEdges\">\n",
+        w = cols * scale,
+        h = rows * scale
+    );
+
+    for row in 0..rows {
+        let mut col = 0;
+
+        while col < cols {
+            let rgb = pixel(row, col);
+            let run = (col..cols).take_while(|c| pixel(row, *c) == rgb).count();
+            let [r, g, b] = rgb;
+
+            let _ = writeln!(image, "<rect x=\"{col}\" y=\"{row}\" width=\"{run}\" height=\"1\" fill=\"#{r:02x}{g:02x}{b:02x}\"/>");
+            col += run;
+        }
+    }
+
+    image += "</svg>\n";
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn images() {
+        let pixel = |row, col| match (row, col) {
+            (0, 0) => WHITE,
+            _ => BLACK
+        };
+
+        let image = ppm(1, 2, 2, pixel);
+        assert!(image.starts_with(b"P6\n4 2\n255\n"));
+        assert_eq!(&image[11..], [WHITE, WHITE, BLACK, BLACK, WHITE, WHITE, BLACK, BLACK].concat());
+
+        let image = svg(2, 3, 4, pixel);
+        assert!(image.contains("width=\"12\" height=\"8\" viewBox=\"0 0 3 2\""));
+        assert!(image.contains("<rect x=\"0\" y=\"0\" width=\"1\" height=\"1\" fill=\"#ffffff\"/>\n<rect x=\"1\" y=\"0\" width=\"2\""));
+        assert!(image.contains("<rect x=\"0\" y=\"1\" width=\"3\" height=\"1\" fill=\"#000000\"/>"));
+    }
+
+    #[test]
+    fn recording() {
+        let dir = std::env::temp_dir().join(format!("aoc_common_viz_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("frame_00009.ppm"), "stale").unwrap();
+        std::fs::write(dir.join("notes.txt"), "kept").unwrap();
+
+        let grid = Grid::from_rows([vec![true, false], vec![false, true]]);
+        let mut recorder = Recorder::new(&dir, FrameFormat::Svg).unwrap().every(2);
+
+        for step in 0..5 {
+            recorder.grid(format_args!("step {step}"), &grid, |on| if *on { WHITE } else { BLACK });
+        }
+
+        assert_eq!(recorder.written(), 3);
+        assert!(!dir.join("frame_00009.ppm").exists());
+        assert!(dir.join("notes.txt").exists());
+        assert!(dir.join("frame_00002.svg").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.join(INDEX)).unwrap(),
+            "frame_00000.svg\tstep 0\nframe_00001.svg\tstep 2\nframe_00002.svg\tstep 4\n"
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
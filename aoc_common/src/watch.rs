@@ -0,0 +1,219 @@
+//! Re-running a day whenever its source or inputs change.
+//!
+//! `watch --day N` polls `src/day_NN.rs` and the day's input files (plain, encrypted and
+//! example) for changes to their modification times. Whenever one changes, it rebuilds and runs
+//! the day's tests (its `derive_tests!` and [example](crate::examples) tests) followed by the
+//! real input, and prints how each answer compares to the previous run and the verified one.
+//!
+//! The runner can't rebuild itself, so both steps go through `cargo`; polling keeps this `std`
+//! only, and working anywhere that `cargo` does.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime}
+};
+
+use crate::{
+    answers::{self, Answers},
+    cli::Options,
+    report::Record,
+    runner::{paint, Part, Year}
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// How long to wait after noticing a change, so that editors can finish saving.
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// The files to watch for `day` of the year crate at `root`.
+pub fn watched(root: &Path, day: u8) -> Vec<PathBuf> {
+    let src = root.join("src");
+
+    vec![
+        src.join(format!("day_{day:02}.rs")),
+        src.join(format!("inputs/{day:02}.txt")),
+        src.join(format!("inputs/{day:02}.txt.enc")),
+        src.join(format!("inputs/test_{day:02}.txt")),
+    ]
+}
+
+/// The modification times of `paths`, with `None` for any that don't exist.
+pub fn snapshot(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| std::fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .collect()
+}
+
+/// `cargo`, as the one that built this binary (if run through it) or from the `PATH`.
+fn cargo() -> Command {
+    Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+}
+
+/// Build and run the day's tests, returning whether they passed.
+fn run_tests(year: &Year, day: u8) -> Result<bool, String> {
+    let status = cargo()
+        .args(["test", "--quiet", "--lib", "--manifest-path"])
+        .arg(Path::new(year.root).join("Cargo.toml"))
+        .args(["--", &format!("day_{day:02}")])
+        .status()
+        .map_err(|err| format!("Could not run cargo: {err}"))?;
+
+    Ok(status.success())
+}
+
+/// Build the runner and solve the day's real input with it.
+fn run_real(year: &Year, day: u8, options: &Options) -> Result<Vec<Record>, String> {
+    let root = Path::new(year.root);
+    let workspace = root.parent().unwrap_or(root);
+
+    let exe = std::env::current_exe().map_err(|err| format!("Could not find the runner: {err}"))?;
+    let bin = exe.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+
+    let mut command = cargo();
+    command
+        .args(["run", "--quiet", "--manifest-path"])
+        .arg(workspace.join("Cargo.toml"))
+        .args(["--bin", &bin]);
+
+    // Stick to the profile the watcher itself was built with.
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+
+    command.args(["--", "run", "--year", &year.year.to_string(), "--day", &day.to_string(), "--format", "json"]);
+
+    if let Some(part) = options.part {
+        command.args(["--part", &part.to_string()]);
+    }
+
+    let output = command
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|err| format!("Could not run cargo: {err}"))?;
+
+    if !output.status.success() {
+        return Err("The run failed.".to_owned());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(Record::from_json)
+        .collect())
+}
+
+/// Describe a new answer relative to the previous run's and the verified one.
+pub fn describe(answer: &str, previous: Option<&str>, verified: Option<&str>) -> String {
+    let change = match previous {
+        None => String::new(),
+        Some(previous) if previous == answer => " (unchanged)".to_owned(),
+        Some(previous) => format!(" (was {})", answers::escape(previous))
+    };
+
+    let check = match verified {
+        None => String::new(),
+        Some(verified) if verified == answer => format!(" {}", paint(&"✓ verified", "32")),
+        Some(verified) => format!(" {}", paint(&format!("✗ verified answer is {}", answers::escape(verified)), "31"))
+    };
+
+    format!("{}{change}{check}", answers::escape(answer))
+}
+
+/// Rebuild and rerun the day whenever its files change, until interrupted.
+pub fn watch(year: &Year, options: &Options) -> Result<(), String> {
+    let day = match &options.days {
+        Some(days) if days.start() == days.end() => *days.start(),
+        _ => return Err("The watch command needs exactly one day (e.g. watch 7).".to_owned())
+    };
+
+    if year.day(day).is_none() {
+        return Err(format!("Day {day} isn't registered for {}; create it with the new command first.", year.year));
+    }
+
+    let paths = watched(Path::new(year.root), day);
+    let mut previous: HashMap<Part, String> = HashMap::new();
+    let mut seen = None;
+
+    println!("Watching {} (press Ctrl-C to stop.)", paths.iter().map(|path| year.path(path).display().to_string()).collect::<Vec<_>>().join(", "));
+
+    loop {
+        let current = snapshot(&paths);
+
+        if seen.as_ref() == Some(&current) {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+
+        // Let the change settle, then use the times from after it did.
+        if seen.is_some() {
+            thread::sleep(SETTLE_TIME);
+        }
+
+        seen = Some(snapshot(&paths));
+        println!("\n{}", paint(&format!("=== Day {day} of {} ===", year.year), "1"));
+
+        match run_tests(year, day)? {
+            true => println!("{}", paint(&"Tests passed.", "32")),
+            false => println!("{}", paint(&"Tests failed.", "31"))
+        }
+
+        let verified = Answers::load(year.path(year.answers))?;
+
+        match run_real(year, day, options) {
+            Ok(records) => {
+                for record in records {
+                    let answer = record.answer.as_deref().unwrap_or("");
+
+                    let line = match record.status {
+                        "solved" => describe(answer, previous.get(&record.part).map(String::as_str), verified.get(day, record.part)),
+                        "unimplemented" => "unimplemented".to_owned(),
+                        status => format!("{} {answer}", status.replace('_', " "))
+                    };
+
+                    println!("Part {}: {line}", record.part);
+
+                    if record.status == "solved" {
+                        previous.insert(record.part, answer.to_owned());
+                    }
+                }
+            },
+            Err(err) => println!("{}", paint(&err, "31"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watched_files() {
+        let dir = std::env::temp_dir().join(format!("aoc_common_watch_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("src/inputs")).unwrap();
+
+        let paths = watched(&dir, 7);
+        assert!(paths[0].ends_with("src/day_07.rs"));
+        assert!(paths[3].ends_with("src/inputs/test_07.txt"));
+
+        let before = snapshot(&paths);
+        assert_eq!(before, [None; 4]);
+
+        std::fs::write(&paths[0], "use super::*;\n").unwrap();
+        let after = snapshot(&paths);
+        assert_ne!(after, before);
+        assert_eq!(snapshot(&paths), after);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn descriptions() {
+        // Whether the checks are coloured depends on where the tests' stdout goes.
+        assert_eq!(describe("42", None, None), "42");
+        assert_eq!(describe("42", Some("42"), None), "42 (unchanged)");
+        assert_eq!(describe("42", Some("41"), Some("42")), format!("42 (was 41) {}", paint(&"✓ verified", "32")));
+        assert_eq!(describe("a\nb", None, Some("c")), format!("a\\nb {}", paint(&"✗ verified answer is c", "31")));
+    }
+}